@@ -16,6 +16,21 @@ impl ActivationTimes {
         Self::new(None, None)
     }
 
+    /// Returns true if the given time lies within the activation period.
+    ///
+    /// A missing start or end is treated as open-ended. Without any defined
+    /// time (`AA NONE`), the airspace is inactive. Time zone offsets are
+    /// taken into account.
+    pub fn is_active_at(&self, time: &iso8601::DateTime) -> bool {
+        if self.start.is_none() && self.end.is_none() {
+            return false;
+        }
+        let time = timestamp_millis(time);
+        self.start
+            .is_none_or(|start| timestamp_millis(&start) <= time)
+            && self.end.is_none_or(|end| time <= timestamp_millis(&end))
+    }
+
     /// Writes the activation times in OpenAir format.
     pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
         match (self.start, self.end) {
@@ -27,6 +42,36 @@ impl ActivationTimes {
     }
 }
 
+/// Milliseconds since 1970-01-01T00:00:00Z.
+fn timestamp_millis(datetime: &iso8601::DateTime) -> i64 {
+    let days = match datetime.date {
+        iso8601::Date::YMD { year, month, day } => days_from_civil(year, month, day),
+        iso8601::Date::Ordinal { year, ddd } => days_from_civil(year, 1, 1) + i64::from(ddd) - 1,
+        iso8601::Date::Week { year, ww, d } => {
+            // Week 1 is the week containing January 4th
+            let jan4 = days_from_civil(year, 1, 4);
+            let weekday = (jan4 + 3).rem_euclid(7); // 0 = Monday
+            jan4 - weekday + (i64::from(ww) - 1) * 7 + i64::from(d) - 1
+        }
+    };
+    let time = datetime.time;
+    let seconds =
+        i64::from(time.hour) * 3600 + i64::from(time.minute) * 60 + i64::from(time.second);
+    let offset = i64::from(time.tz_offset_hours) * 3600 + i64::from(time.tz_offset_minutes) * 60;
+    (days * 86_400 + seconds - offset) * 1000 + i64::from(time.millisecond)
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
 impl FromStr for ActivationTimes {
     type Err = String;
 
@@ -231,4 +276,48 @@ mod tests {
         let times = "NONE".parse::<ActivationTimes>().unwrap();
         assert_eq!(write_activation_times(&times), "NONE");
     }
+
+    #[test]
+    fn is_active_at() {
+        let at = |s: &str| iso8601::datetime(s).unwrap();
+        let times = "2023-12-16T12:00Z/2023-12-16T13:00Z"
+            .parse::<ActivationTimes>()
+            .unwrap();
+        assert!(!times.is_active_at(&at("2023-12-16T11:59:59Z")));
+        assert!(times.is_active_at(&at("2023-12-16T12:00Z")));
+        assert!(times.is_active_at(&at("2023-12-16T13:00Z")));
+        assert!(!times.is_active_at(&at("2023-12-16T13:00:00.001Z")));
+        // Time zone offsets
+        assert!(times.is_active_at(&at("2023-12-16T13:30+01:00")));
+        assert!(!times.is_active_at(&at("2023-12-16T13:30-01:00")));
+        assert!(times.is_active_at(&at("2023-12-16T06:45-05:30")));
+        // Ordinal and week dates
+        assert!(times.is_active_at(&at("2023-350T12:30Z")));
+        assert!(times.is_active_at(&at("2023-W50-6T12:30Z")));
+        assert!(!times.is_active_at(&at("2023-W50-5T12:30Z")));
+
+        let times = "2024-12-17T00:00Z/NONE".parse::<ActivationTimes>().unwrap();
+        assert!(!times.is_active_at(&at("2024-12-16T23:59Z")));
+        assert!(times.is_active_at(&at("2099-01-01T00:00Z")));
+
+        let times = "NONE/2024-12-18T00:00Z".parse::<ActivationTimes>().unwrap();
+        assert!(times.is_active_at(&at("1999-01-01T00:00Z")));
+        assert!(!times.is_active_at(&at("2024-12-18T00:01Z")));
+
+        // 24:00 is the end of the day
+        let times = "2024-12-17T00:00Z/2024-12-17T24:00Z"
+            .parse::<ActivationTimes>()
+            .unwrap();
+        assert!(times.is_active_at(&at("2024-12-17T23:59Z")));
+        assert!(!times.is_active_at(&at("2024-12-18T00:01Z")));
+
+        assert!(!ActivationTimes::none().is_active_at(&at("2024-12-17T12:00Z")));
+    }
+
+    #[test]
+    fn civil_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+    }
 }
@@ -0,0 +1,340 @@
+use std::fmt;
+
+/// Airspace type (AY extension record).
+///
+/// Covers the values defined by the Naviter OpenAir 2.0 extension. The short
+/// forms used by OpenAIP exports (`R`, `Q`, `P`) are accepted as aliases for
+/// the corresponding long forms, and written in the long form unless
+/// [`TypeSpelling::Short`] is selected. Known values are written in upper
+/// case, unknown values are kept verbatim in [`AirspaceType::Other`].
+///
+/// [`TypeSpelling::Short`]: crate::TypeSpelling::Short
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AirspaceType {
+    /// Unclassified
+    Unclassified,
+    /// Area control center sector
+    AccSector,
+    /// Air defense identification zone
+    Adiz,
+    /// Alert area
+    Alert,
+    /// Aerial sporting and recreational activity area
+    Asra,
+    /// Aerodrome traffic zone
+    Atz,
+    /// Airway
+    Awy,
+    /// Control area
+    Cta,
+    /// Control zone
+    Ctr,
+    /// Custom / user defined airspace
+    Custom,
+    /// Danger area
+    Danger,
+    /// Flight information region
+    Fir,
+    /// Flight information service sector
+    Fis,
+    /// Gliding sector
+    GlidingSector,
+    /// Helicopter traffic zone
+    Htz,
+    /// Lower traffic area
+    Lta,
+    /// Military aerodrome traffic zone
+    Matz,
+    /// Military training area
+    Mta,
+    /// Military training route
+    Mtr,
+    /// National park
+    NationalPark,
+    /// No-fly zone
+    NoFly,
+    /// Overflight restriction
+    OverflightRestriction,
+    /// Prohibited area
+    Prohibited,
+    /// Restricted area
+    Restricted,
+    /// Radio mandatory zone
+    Rmz,
+    /// Sport / recreational area
+    Sport,
+    /// Temporary flight restriction
+    Tfr,
+    /// Traffic information area
+    Tia,
+    /// Traffic information zone
+    Tiz,
+    /// Terminal control area
+    Tma,
+    /// Transponder mandatory zone
+    Tmz,
+    /// Temporary reserved area
+    Tra,
+    /// Temporary reserved area (free routing)
+    TraFr,
+    /// Transponder recommended zone
+    Trz,
+    /// Temporary segregated area
+    Tsa,
+    /// Upper flight information region
+    Uir,
+    /// Upper traffic area
+    Uta,
+    /// VFR route
+    VfrRoute,
+    /// VFR sector
+    VfrSector,
+    /// Warning area
+    Warning,
+    /// Other (unknown type, kept verbatim)
+    Other(String),
+}
+
+impl fmt::Display for AirspaceType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AirspaceType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_str())
+    }
+}
+
+impl AirspaceType {
+    /// Parse an AY record value. Matching is case-insensitive and never fails,
+    /// unknown values are returned as [`AirspaceType::Other`].
+    pub fn parse(data: &str) -> Self {
+        match data.to_ascii_uppercase().as_str() {
+            "UNCLASSIFIED" => Self::Unclassified,
+            "ACCSEC" => Self::AccSector,
+            "ADIZ" => Self::Adiz,
+            "ALERT" => Self::Alert,
+            "ASRA" => Self::Asra,
+            "ATZ" => Self::Atz,
+            "AWY" => Self::Awy,
+            "CTA" => Self::Cta,
+            "CTR" => Self::Ctr,
+            "CUSTOM" => Self::Custom,
+            "DANGER" | "Q" => Self::Danger,
+            "FIR" => Self::Fir,
+            "FIS" => Self::Fis,
+            "GSEC" => Self::GlidingSector,
+            "HTZ" => Self::Htz,
+            "LTA" => Self::Lta,
+            "MATZ" => Self::Matz,
+            "MTA" => Self::Mta,
+            "MTR" => Self::Mtr,
+            "NATIONAL_PARK" => Self::NationalPark,
+            "NOFLY" => Self::NoFly,
+            "OFR" => Self::OverflightRestriction,
+            "PROHIBITED" | "P" => Self::Prohibited,
+            "RESTRICTED" | "R" => Self::Restricted,
+            "RMZ" => Self::Rmz,
+            "SPORT" => Self::Sport,
+            "TFR" => Self::Tfr,
+            "TIA" => Self::Tia,
+            "TIZ" => Self::Tiz,
+            "TMA" => Self::Tma,
+            "TMZ" => Self::Tmz,
+            "TRA" => Self::Tra,
+            "TRAFR" => Self::TraFr,
+            "TRZ" => Self::Trz,
+            "TSA" => Self::Tsa,
+            "UIR" => Self::Uir,
+            "UTA" => Self::Uta,
+            "VFRR" => Self::VfrRoute,
+            "VFRSEC" => Self::VfrSector,
+            "WARNING" => Self::Warning,
+            _ => Self::Other(data.to_string()),
+        }
+    }
+
+    /// Returns the OpenAir string representation for this type.
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Unclassified => "UNCLASSIFIED",
+            Self::AccSector => "ACCSEC",
+            Self::Adiz => "ADIZ",
+            Self::Alert => "ALERT",
+            Self::Asra => "ASRA",
+            Self::Atz => "ATZ",
+            Self::Awy => "AWY",
+            Self::Cta => "CTA",
+            Self::Ctr => "CTR",
+            Self::Custom => "CUSTOM",
+            Self::Danger => "DANGER",
+            Self::Fir => "FIR",
+            Self::Fis => "FIS",
+            Self::GlidingSector => "GSEC",
+            Self::Htz => "HTZ",
+            Self::Lta => "LTA",
+            Self::Matz => "MATZ",
+            Self::Mta => "MTA",
+            Self::Mtr => "MTR",
+            Self::NationalPark => "NATIONAL_PARK",
+            Self::NoFly => "NOFLY",
+            Self::OverflightRestriction => "OFR",
+            Self::Prohibited => "PROHIBITED",
+            Self::Restricted => "RESTRICTED",
+            Self::Rmz => "RMZ",
+            Self::Sport => "SPORT",
+            Self::Tfr => "TFR",
+            Self::Tia => "TIA",
+            Self::Tiz => "TIZ",
+            Self::Tma => "TMA",
+            Self::Tmz => "TMZ",
+            Self::Tra => "TRA",
+            Self::TraFr => "TRAFR",
+            Self::Trz => "TRZ",
+            Self::Tsa => "TSA",
+            Self::Uir => "UIR",
+            Self::Uta => "UTA",
+            Self::VfrRoute => "VFRR",
+            Self::VfrSector => "VFRSEC",
+            Self::Warning => "WARNING",
+            Self::Other(s) => s,
+        }
+    }
+
+    /// Returns the short form (`R`, `Q`, `P`) for restricted, danger and
+    /// prohibited areas, and the same as [`Self::to_str`] for other types.
+    pub fn to_short_str(&self) -> &str {
+        match self {
+            Self::Restricted => "R",
+            Self::Danger => "Q",
+            Self::Prohibited => "P",
+            _ => self.to_str(),
+        }
+    }
+
+    /// Returns true for airspace types under air traffic control.
+    pub fn is_controlled(&self) -> bool {
+        matches!(
+            self,
+            Self::AccSector
+                | Self::Atz
+                | Self::Awy
+                | Self::Cta
+                | Self::Ctr
+                | Self::Htz
+                | Self::Lta
+                | Self::Matz
+                | Self::Tma
+                | Self::Uta
+        )
+    }
+
+    /// Returns true for airspace types that restrict or prohibit entry, either
+    /// permanently or while active.
+    pub fn is_restricted(&self) -> bool {
+        matches!(
+            self,
+            Self::Adiz
+                | Self::Alert
+                | Self::Danger
+                | Self::Mta
+                | Self::Mtr
+                | Self::NationalPark
+                | Self::NoFly
+                | Self::OverflightRestriction
+                | Self::Prohibited
+                | Self::Restricted
+                | Self::Tfr
+                | Self::Tra
+                | Self::TraFr
+                | Self::Tsa
+                | Self::Warning
+        )
+    }
+
+    /// Returns true for airspace types that are only informational, i.e.
+    /// neither controlled nor restricted.
+    pub fn is_informational(&self) -> bool {
+        matches!(
+            self,
+            Self::Asra
+                | Self::Fir
+                | Self::Fis
+                | Self::GlidingSector
+                | Self::Rmz
+                | Self::Sport
+                | Self::Tia
+                | Self::Tiz
+                | Self::Tmz
+                | Self::Trz
+                | Self::Uir
+                | Self::VfrRoute
+                | Self::VfrSector
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_known() {
+        assert_eq!(AirspaceType::parse("CTR"), AirspaceType::Ctr);
+        assert_eq!(AirspaceType::parse("tma"), AirspaceType::Tma);
+        assert_eq!(AirspaceType::parse("GSEC"), AirspaceType::GlidingSector);
+        assert_eq!(
+            AirspaceType::parse("OFR"),
+            AirspaceType::OverflightRestriction
+        );
+        assert_eq!(AirspaceType::parse("RESTRICTED"), AirspaceType::Restricted);
+    }
+
+    #[test]
+    fn parse_aliases() {
+        assert_eq!(AirspaceType::parse("R"), AirspaceType::Restricted);
+        assert_eq!(AirspaceType::parse("Q"), AirspaceType::Danger);
+        assert_eq!(AirspaceType::parse("P"), AirspaceType::Prohibited);
+    }
+
+    #[test]
+    fn parse_other() {
+        assert_eq!(
+            AirspaceType::parse("NONE"),
+            AirspaceType::Other("NONE".to_string())
+        );
+        assert_eq!(AirspaceType::parse("NONE").to_str(), "NONE");
+    }
+
+    #[test]
+    fn to_str_roundtrip() {
+        for s in ["CTR", "TMA", "GSEC", "FIS", "RESTRICTED", "VFRSEC", "TRAFR"] {
+            assert_eq!(AirspaceType::parse(s).to_str(), s);
+        }
+    }
+
+    #[test]
+    fn short_str() {
+        assert_eq!(AirspaceType::parse("r").to_short_str(), "R");
+        assert_eq!(AirspaceType::Danger.to_short_str(), "Q");
+        assert_eq!(AirspaceType::parse("P").to_short_str(), "P");
+        assert_eq!(AirspaceType::Ctr.to_short_str(), "CTR");
+    }
+
+    #[test]
+    fn categories() {
+        assert!(AirspaceType::Ctr.is_controlled());
+        assert!(!AirspaceType::Ctr.is_restricted());
+        assert!(AirspaceType::Restricted.is_restricted());
+        assert!(AirspaceType::Fis.is_informational());
+        assert!(!AirspaceType::Fis.is_controlled());
+
+        let other = AirspaceType::Other("XYZ".to_string());
+        assert!(!other.is_controlled());
+        assert!(!other.is_restricted());
+        assert!(!other.is_informational());
+    }
+}
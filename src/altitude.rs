@@ -1,5 +1,7 @@
 use std::{fmt, io::Write};
 
+use crate::writer::WriterOptions;
+
 /// Altitude, either ground or a certain height AMSL in feet.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
@@ -55,13 +57,23 @@ fn is_agl_suffix(s: &str) -> bool {
 
 impl Altitude {
     /// Writes the altitude in OpenAir format.
-    pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+    pub fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        self.write_with(writer, &WriterOptions::default())
+    }
+
+    /// Writes the altitude in OpenAir format, using the spellings configured
+    /// in the options.
+    pub fn write_with<W: Write>(
+        &self,
+        mut writer: W,
+        options: &WriterOptions,
+    ) -> std::io::Result<()> {
         match self {
-            Self::Gnd => write!(writer, "GND"),
-            Self::FeetAmsl(n) => write!(writer, "{n}ft AMSL"),
+            Self::Gnd => write!(writer, "{}", options.ground.as_str()),
+            Self::FeetAmsl(n) => write!(writer, "{n}ft {}", options.msl.as_str()),
             Self::FeetAgl(n) => write!(writer, "{n}ft AGL"),
             Self::FlightLevel(n) => write!(writer, "FL{n}"),
-            Self::Unlimited => write!(writer, "UNLIM"),
+            Self::Unlimited => write!(writer, "{}", options.unlimited.as_str()),
             Self::Other(s) => write!(writer, "{s}"),
         }
     }
@@ -347,4 +359,25 @@ mod tests {
             "custom"
         );
     }
+
+    #[test]
+    fn write_spellings() {
+        use crate::writer::{GroundSpelling, MslSpelling, UnlimitedSpelling};
+
+        let options = WriterOptions {
+            ground: GroundSpelling::Sfc,
+            msl: MslSpelling::Msl,
+            unlimited: UnlimitedSpelling::Unl,
+            ..Default::default()
+        };
+        let write = |altitude: &Altitude| {
+            let mut buf = Vec::new();
+            altitude.write_with(&mut buf, &options).unwrap();
+            String::from_utf8(buf).unwrap()
+        };
+        assert_eq!(write(&Altitude::Gnd), "SFC");
+        assert_eq!(write(&Altitude::FeetAmsl(5000)), "5000ft MSL");
+        assert_eq!(write(&Altitude::FeetAgl(1500)), "1500ft AGL");
+        assert_eq!(write(&Altitude::Unlimited), "UNL");
+    }
 }
@@ -0,0 +1,193 @@
+use crate::{Airspace, Coord};
+
+/// A bounding box in degrees.
+///
+/// Boxes crossing the antimeridian have a `min_lng` larger than their
+/// `max_lng` (as in GeoJSON), e.g. `min_lng: 170.0, max_lng: -170.0` spans
+/// 20° of longitude. Boxes around a pole span all longitudes from -180 to
+/// 180.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub min_lng: f64,
+    pub max_lat: f64,
+    pub max_lng: f64,
+}
+
+impl BoundingBox {
+    /// A bounding box containing a single coordinate.
+    pub fn from_coord(coord: &Coord) -> Self {
+        Self::from_degrees(coord.lat(), coord.lng())
+    }
+
+    pub(crate) fn from_degrees(lat: f64, lng: f64) -> Self {
+        Self {
+            min_lat: lat,
+            min_lng: lng,
+            max_lat: lat,
+            max_lng: lng,
+        }
+    }
+
+    /// The bounding box of all airspaces, or `None` if there are no
+    /// airspaces with a non-empty geometry.
+    pub fn from_airspaces<'a, I: IntoIterator<Item = &'a Airspace>>(airspaces: I) -> Option<Self> {
+        airspaces
+            .into_iter()
+            .filter_map(Airspace::bbox)
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Extend the bounding box to contain the coordinate, towards the side
+    /// requiring the smaller extension in longitude.
+    pub fn extend(&mut self, coord: &Coord) {
+        self.extend_degrees(coord.lat(), coord.lng());
+    }
+
+    pub(crate) fn extend_degrees(&mut self, lat: f64, lng: f64) {
+        self.min_lat = self.min_lat.min(lat);
+        self.max_lat = self.max_lat.max(lat);
+        if self.contains_lng(lng) {
+            return;
+        }
+        // Extend towards the closer side, possibly across the antimeridian
+        let east = (lng - self.max_lng).rem_euclid(360.0);
+        let west = (self.min_lng - lng).rem_euclid(360.0);
+        if east <= west {
+            self.max_lng = lng;
+        } else {
+            self.min_lng = lng;
+        }
+    }
+
+    /// The smallest bounding box containing both bounding boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        let (min_lng, max_lng) = [
+            (self.min_lng, self.max_lng),
+            (other.min_lng, other.max_lng),
+            (self.min_lng, other.max_lng),
+            (other.min_lng, self.max_lng),
+        ]
+        .into_iter()
+        .filter(|&(min, max)| {
+            lng_range_contains(min, max, self.min_lng, self.max_lng)
+                && lng_range_contains(min, max, other.min_lng, other.max_lng)
+        })
+        .min_by(|a, b| lng_width(a.0, a.1).total_cmp(&lng_width(b.0, b.1)))
+        .unwrap_or((-180.0, 180.0));
+        Self {
+            min_lat: self.min_lat.min(other.min_lat),
+            min_lng,
+            max_lat: self.max_lat.max(other.max_lat),
+            max_lng,
+        }
+    }
+
+    /// Returns true if the box crosses the antimeridian.
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_lng > self.max_lng
+    }
+
+    /// Returns true if the coordinate lies inside the bounding box (or on
+    /// its edge).
+    pub fn contains(&self, coord: &Coord) -> bool {
+        (self.min_lat..=self.max_lat).contains(&coord.lat()) && self.contains_lng(coord.lng())
+    }
+
+    /// Returns true if the bounding boxes overlap (or touch).
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min_lat <= other.max_lat
+            && other.min_lat <= self.max_lat
+            && (self.contains_lng(other.min_lng) || other.contains_lng(self.min_lng))
+    }
+
+    fn contains_lng(&self, lng: f64) -> bool {
+        lng_range_contains(self.min_lng, self.max_lng, lng, lng)
+    }
+}
+
+/// Width of a longitude range in degrees, taking ranges across the
+/// antimeridian into account.
+fn lng_width(min: f64, max: f64) -> f64 {
+    if min <= max {
+        max - min
+    } else {
+        max - min + 360.0
+    }
+}
+
+/// Returns true if the longitude range `min..=max` contains the range
+/// `inner_min..=inner_max`.
+fn lng_range_contains(min: f64, max: f64, inner_min: f64, inner_max: f64) -> bool {
+    if lng_width(min, max) >= 360.0 {
+        return true;
+    }
+    (inner_min - min).rem_euclid(360.0) + lng_width(inner_min, inner_max) <= lng_width(min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extend_and_union() {
+        let mut bbox = BoundingBox::from_coord(&Coord::from_degrees(47.0, 8.0));
+        bbox.extend(&Coord::from_degrees(46.0, 9.0));
+        assert_eq!(
+            bbox,
+            BoundingBox {
+                min_lat: 46.0,
+                min_lng: 8.0,
+                max_lat: 47.0,
+                max_lng: 9.0,
+            }
+        );
+        assert!(bbox.contains(&Coord::from_degrees(46.5, 8.5)));
+        assert!(bbox.contains(&Coord::from_degrees(47.0, 9.0)));
+        assert!(!bbox.contains(&Coord::from_degrees(47.1, 8.5)));
+
+        let other = BoundingBox::from_coord(&Coord::from_degrees(48.0, 7.0));
+        assert!(!bbox.intersects(&other));
+        let union = bbox.union(&other);
+        assert_eq!((union.min_lat, union.max_lat), (46.0, 48.0));
+        assert_eq!((union.min_lng, union.max_lng), (7.0, 9.0));
+        assert!(union.intersects(&bbox));
+        assert!(union.intersects(&other));
+    }
+
+    #[test]
+    fn antimeridian() {
+        let mut bbox = BoundingBox::from_coord(&Coord::from_degrees(-17.0, 178.0));
+        bbox.extend(&Coord::from_degrees(-18.0, -179.0));
+        assert_eq!((bbox.min_lng, bbox.max_lng), (178.0, -179.0));
+        assert!(bbox.crosses_antimeridian());
+        assert!(bbox.contains(&Coord::from_degrees(-17.5, 180.0)));
+        assert!(bbox.contains(&Coord::from_degrees(-17.5, -179.5)));
+        assert!(!bbox.contains(&Coord::from_degrees(-17.5, 0.0)));
+
+        // Extending westwards
+        bbox.extend(&Coord::from_degrees(-17.0, 170.0));
+        assert_eq!((bbox.min_lng, bbox.max_lng), (170.0, -179.0));
+
+        let east = BoundingBox::from_coord(&Coord::from_degrees(-17.0, -170.0));
+        assert!(!bbox.intersects(&east));
+        let union = bbox.union(&east);
+        assert_eq!((union.min_lng, union.max_lng), (170.0, -170.0));
+        assert!(union.intersects(&bbox) && union.intersects(&east));
+
+        let west = BoundingBox::from_coord(&Coord::from_degrees(-17.0, 175.0));
+        assert_eq!(bbox.union(&west), bbox);
+
+        // Boxes covering more than half of the earth
+        let wide = BoundingBox {
+            min_lat: 0.0,
+            min_lng: -100.0,
+            max_lat: 1.0,
+            max_lng: 100.0,
+        };
+        let union = wide.union(&bbox);
+        assert_eq!((union.min_lng, union.max_lng), (-100.0, -179.0));
+    }
+}
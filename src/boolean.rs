@@ -0,0 +1,719 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Arc, ArcSegment, Coord, Direction, Geometry, PolygonSegment, geodesy, geometry};
+
+/// Tolerance in metres for flattening arcs before combining geometries.
+const ARC_TOLERANCE: f64 = 1.0;
+
+/// Distance in degrees below which points are considered to lie on an edge.
+const SNAP: f64 = 5e-7;
+
+/// Boolean operation on two geometries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Intersection,
+    Union,
+    Difference,
+}
+
+impl Geometry {
+    /// The parts covered by both geometries.
+    ///
+    /// Geometries are combined by flattening them (see [`Self::to_points`]),
+    /// then arcs and circles are restored on the parts of the boundary that
+    /// come from an arc. Arcs that survive unchanged are kept as they are,
+    /// cut arcs keep their centerpoint and radius, with their new angles
+    /// rounded to 0.01°. The resulting polygons run clockwise. Both
+    /// geometries must not intersect themselves, span more than 180° of
+    /// longitude or enclose a pole.
+    ///
+    /// ```
+    /// use openair::{Coord, Geometry};
+    ///
+    /// let a = Geometry::rectangle(Coord::from_degrees(47.0, 8.0), Coord::from_degrees(46.0, 9.0));
+    /// let b = Geometry::rectangle(Coord::from_degrees(46.5, 8.5), Coord::from_degrees(45.5, 9.5));
+    /// let parts = a.intersection(&b);
+    /// assert_eq!(parts.len(), 1);
+    /// assert_eq!(parts[0].to_string(), "Polygon[4]");
+    /// assert!(parts[0].contains(&Coord::from_degrees(46.25, 8.75)));
+    /// assert!(!parts[0].contains(&Coord::from_degrees(46.75, 8.25)));
+    /// ```
+    pub fn intersection(&self, other: &Geometry) -> Vec<Geometry> {
+        overlay(self, other, Operation::Intersection)
+    }
+
+    /// The parts covered by either geometry, see [`Self::intersection`].
+    ///
+    /// Adjacent geometries are merged, disjoint geometries are returned
+    /// separately.
+    pub fn union(&self, other: &Geometry) -> Vec<Geometry> {
+        overlay(self, other, Operation::Union)
+    }
+
+    /// The parts of this geometry not covered by the other geometry, see
+    /// [`Self::intersection`].
+    ///
+    /// Holes are connected to the surrounding polygon by a cut of zero
+    /// width, as polygons cannot have holes.
+    pub fn difference(&self, other: &Geometry) -> Vec<Geometry> {
+        overlay(self, other, Operation::Difference)
+    }
+}
+
+/// The arc an edge of a flattened geometry belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Tag {
+    /// Index of the geometry (0 or 1)
+    source: usize,
+    /// Index of the polygon segment, 0 for circles
+    segment: usize,
+    /// True if the edge runs against the direction of the arc
+    reversed: bool,
+}
+
+/// An edge of a flattened ring, with the arc it belongs to (if any).
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    from: Coord,
+    to: Coord,
+    tag: Option<Tag>,
+}
+
+impl Edge {
+    fn reversed(self) -> Self {
+        Self {
+            from: self.to,
+            to: self.from,
+            tag: self.tag.map(|tag| Tag {
+                reversed: !tag.reversed,
+                ..tag
+            }),
+        }
+    }
+}
+
+/// Converts coordinates to planar (longitude, latitude) points, unwrapping
+/// longitudes around a reference longitude.
+#[derive(Debug, Clone, Copy)]
+struct Plane {
+    lng: f64,
+}
+
+impl Plane {
+    fn point(&self, coord: &Coord) -> (f64, f64) {
+        (
+            self.lng + geodesy::normalize_longitude(coord.lng() - self.lng),
+            coord.lat(),
+        )
+    }
+
+    fn coord(&self, (x, y): (f64, f64)) -> Coord {
+        Coord::from_degrees(y, geodesy::normalize_longitude(x))
+    }
+}
+
+fn overlay(a: &Geometry, b: &Geometry, operation: Operation) -> Vec<Geometry> {
+    let (ring_a, ring_b) = (flatten(a, 0), flatten(b, 1));
+    let Some(reference) = ring_a.first().or(ring_b.first()) else {
+        return vec![];
+    };
+    let plane = Plane {
+        lng: reference.from.lng(),
+    };
+    let ring_a = clockwise(ring_a, plane);
+    let ring_b = clockwise(ring_b, plane);
+    let (edges_a, edges_b) = split_edges(&ring_a, &ring_b, plane);
+
+    // Select the edges of the result, keeping the interior on the right
+    let planar_a = ring_a
+        .iter()
+        .map(|e| plane.point(&e.from))
+        .collect::<Vec<_>>();
+    let planar_b = ring_b
+        .iter()
+        .map(|e| plane.point(&e.from))
+        .collect::<Vec<_>>();
+    let keys_b = edges_b
+        .iter()
+        .map(|edge| (edge.from, edge.to))
+        .collect::<HashSet<_>>();
+    let keys_a = edges_a
+        .iter()
+        .map(|edge| (edge.from, edge.to))
+        .collect::<HashSet<_>>();
+    let inside_b = classify(&edges_a, &edges_b, &planar_b, plane);
+    let inside_a = classify(&edges_b, &edges_a, &planar_a, plane);
+    let mut selected = Vec::new();
+    for (edge, inside) in edges_a.iter().zip(inside_b) {
+        let same = keys_b.contains(&(edge.from, edge.to));
+        let opposite = keys_b.contains(&(edge.to, edge.from));
+        let keep = match operation {
+            _ if same => operation != Operation::Difference,
+            _ if opposite => operation == Operation::Difference,
+            Operation::Intersection => inside,
+            Operation::Union | Operation::Difference => !inside,
+        };
+        if keep {
+            selected.push(*edge);
+        }
+    }
+    for (edge, inside) in edges_b.iter().zip(inside_a) {
+        if keys_a.contains(&(edge.from, edge.to)) || keys_a.contains(&(edge.to, edge.from)) {
+            continue;
+        }
+        match operation {
+            Operation::Intersection if inside => selected.push(*edge),
+            Operation::Union if !inside => selected.push(*edge),
+            Operation::Difference if inside => selected.push(edge.reversed()),
+            _ => {}
+        }
+    }
+
+    let rings = link(&selected, plane);
+    let (mut outer, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .partition(|ring| signed_area(ring, plane) < 0.0);
+    for hole in holes {
+        let (x1, y1) = plane.point(&hole[0].from);
+        let (x2, y2) = plane.point(&hole[0].to);
+        let (x, y) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+        let container = outer.iter_mut().find(|ring| {
+            let planar = ring
+                .iter()
+                .map(|e| plane.point(&e.from))
+                .collect::<Vec<_>>();
+            geometry::planar_contains(&planar, x, y)
+        });
+        if let Some(ring) = container {
+            bridge(ring, &hole, plane);
+        }
+    }
+    outer
+        .iter()
+        .map(|ring| restore_arcs(ring, [a, b]))
+        .collect()
+}
+
+/// Flatten the geometry into edges tagged with the arcs they belong to.
+fn flatten(geom: &Geometry, source: usize) -> Vec<Edge> {
+    let points: Vec<(Coord, Option<Tag>)> = match geom {
+        Geometry::Circle { .. } => {
+            let tag = Tag {
+                source,
+                segment: 0,
+                reversed: false,
+            };
+            geom.to_points(ARC_TOLERANCE)
+                .into_iter()
+                .map(|point| (point, Some(tag)))
+                .collect()
+        }
+        Geometry::Polygon { segments } => {
+            let points = geometry::boundary(segments, ARC_TOLERANCE);
+            (0..points.len())
+                .map(|i| {
+                    let (point, segment) = points[i];
+                    let next = points[(i + 1) % points.len()].1;
+                    let is_arc = !matches!(segments[segment], PolygonSegment::Point(_));
+                    let tag = (is_arc && segment == next).then_some(Tag {
+                        source,
+                        segment,
+                        reversed: false,
+                    });
+                    (point, tag)
+                })
+                .collect()
+        }
+    };
+    let edges = (0..points.len())
+        .map(|i| Edge {
+            from: points[i].0,
+            to: points[(i + 1) % points.len()].0,
+            tag: points[i].1,
+        })
+        .filter(|edge| edge.from != edge.to)
+        .collect::<Vec<_>>();
+    let distinct = edges.iter().map(|edge| edge.from).collect::<HashSet<_>>();
+    if distinct.len() < 3 { vec![] } else { edges }
+}
+
+/// Signed area of a ring in the plane, negative for clockwise rings.
+fn signed_area(ring: &[Edge], plane: Plane) -> f64 {
+    ring.iter()
+        .map(|edge| {
+            let ((x1, y1), (x2, y2)) = (plane.point(&edge.from), plane.point(&edge.to));
+            x1 * y2 - x2 * y1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+fn clockwise(ring: Vec<Edge>, plane: Plane) -> Vec<Edge> {
+    if signed_area(&ring, plane) > 0.0 {
+        ring.into_iter().rev().map(Edge::reversed).collect()
+    } else {
+        ring
+    }
+}
+
+/// Split the edges of both rings at their intersections, so that edges of
+/// both rings only meet at their end points.
+fn split_edges(a: &[Edge], b: &[Edge], plane: Plane) -> (Vec<Edge>, Vec<Edge>) {
+    let mut splits_a = vec![Vec::new(); a.len()];
+    let mut splits_b = vec![Vec::new(); b.len()];
+    let bounds = |edge: &Edge| {
+        let ((x1, y1), (x2, y2)) = (plane.point(&edge.from), plane.point(&edge.to));
+        (
+            x1.min(x2) - SNAP,
+            x1.max(x2) + SNAP,
+            y1.min(y2) - SNAP,
+            y1.max(y2) + SNAP,
+        )
+    };
+    let bounds_a = a.iter().map(bounds).collect::<Vec<_>>();
+    let bounds_b = b.iter().map(bounds).collect::<Vec<_>>();
+
+    // Sweep along the longitude, only comparing edges with overlapping
+    // longitude ranges
+    let mut order = (0..a.len())
+        .map(|i| (bounds_a[i], true, i))
+        .chain((0..b.len()).map(|j| (bounds_b[j], false, j)))
+        .collect::<Vec<_>>();
+    order.sort_by(|(p, ..), (q, ..)| p.0.total_cmp(&q.0));
+    for (k, &(bounds, is_a, index)) in order.iter().enumerate() {
+        let (_, max_x, min_y, max_y) = bounds;
+        for &(other, other_is_a, other_index) in &order[k + 1..] {
+            if other.0 > max_x {
+                break;
+            }
+            if other_is_a == is_a || other.2 > max_y || other.3 < min_y {
+                continue;
+            }
+            let (i, j) = if is_a {
+                (index, other_index)
+            } else {
+                (other_index, index)
+            };
+            for point in intersections(&a[i], &b[j], plane) {
+                splits_a[i].push(point);
+                splits_b[j].push(point);
+            }
+        }
+    }
+    (
+        apply_splits(a, splits_a, plane),
+        apply_splits(b, splits_b, plane),
+    )
+}
+
+/// Points where two edges cross or touch, snapped to their end points.
+fn intersections(a: &Edge, b: &Edge, plane: Plane) -> Vec<Coord> {
+    let (p1, p2) = (plane.point(&a.from), plane.point(&a.to));
+    let (q1, q2) = (plane.point(&b.from), plane.point(&b.to));
+    let cross = |(ax, ay): (f64, f64), (bx, by): (f64, f64)| ax * by - ay * bx;
+    let sub = |(ax, ay): (f64, f64), (bx, by): (f64, f64)| (ax - bx, ay - by);
+    let (r, s) = (sub(p2, p1), sub(q2, q1));
+
+    // End points lying on the other edge, including collinear overlaps
+    let mut points = Vec::new();
+    for (point, coord, from, to) in [
+        (q1, b.from, p1, p2),
+        (q2, b.to, p1, p2),
+        (p1, a.from, q1, q2),
+        (p2, a.to, q1, q2),
+    ] {
+        if geometry::segment_distance(point, from, to) <= SNAP {
+            points.push(coord);
+        }
+    }
+    if points.is_empty() {
+        let denominator = cross(r, s);
+        if denominator != 0.0 {
+            let t = cross(sub(q1, p1), s) / denominator;
+            let u = cross(sub(q1, p1), r) / denominator;
+            if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+                points.push(plane.coord((p1.0 + t * r.0, p1.1 + t * r.1)));
+            }
+        }
+    }
+    points
+}
+
+/// Split every edge at the given points, ordered along the edge.
+fn apply_splits(edges: &[Edge], splits: Vec<Vec<Coord>>, plane: Plane) -> Vec<Edge> {
+    let mut result = Vec::with_capacity(edges.len());
+    for (edge, mut points) in edges.iter().zip(splits) {
+        let (from, to) = (plane.point(&edge.from), plane.point(&edge.to));
+        let position = |coord: &Coord| {
+            let (x, y) = plane.point(coord);
+            (x - from.0) * (to.0 - from.0) + (y - from.1) * (to.1 - from.1)
+        };
+        points.sort_by(|p, q| position(p).total_cmp(&position(q)));
+        let mut current = edge.from;
+        for point in points.into_iter().chain([edge.to]) {
+            if point != current && point != edge.from {
+                result.push(Edge {
+                    from: current,
+                    to: point,
+                    tag: edge.tag,
+                });
+                current = point;
+            }
+        }
+    }
+    result
+}
+
+/// Returns for every edge whether it lies inside the other ring.
+///
+/// The edges only cross the other ring at its vertices, so the result is
+/// only computed again after such a vertex.
+fn classify(edges: &[Edge], other: &[Edge], ring: &[(f64, f64)], plane: Plane) -> Vec<bool> {
+    let vertices = other.iter().map(|edge| edge.from).collect::<HashSet<_>>();
+    let mut inside = false;
+    edges
+        .iter()
+        .enumerate()
+        .map(|(i, edge)| {
+            if i == 0 || vertices.contains(&edge.from) {
+                let ((x1, y1), (x2, y2)) = (plane.point(&edge.from), plane.point(&edge.to));
+                inside = ring.len() >= 3
+                    && geometry::planar_contains(ring, (x1 + x2) / 2.0, (y1 + y2) / 2.0);
+            }
+            inside
+        })
+        .collect()
+}
+
+/// Link edges into closed rings. At vertices with several outgoing edges,
+/// the edge turning farthest to the right is followed, which keeps rings
+/// that only touch at a vertex apart.
+fn link(edges: &[Edge], plane: Plane) -> Vec<Vec<Edge>> {
+    let mut outgoing: HashMap<Coord, Vec<usize>> = HashMap::new();
+    for (i, edge) in edges.iter().enumerate() {
+        outgoing.entry(edge.from).or_default().push(i);
+    }
+    let direction = |edge: &Edge| {
+        let ((x1, y1), (x2, y2)) = (plane.point(&edge.from), plane.point(&edge.to));
+        (x2 - x1, y2 - y1)
+    };
+
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let mut ring = vec![edges[start]];
+        let mut current = start;
+        while edges[current].to != edges[start].from {
+            let (dx, dy) = direction(&edges[current]);
+            let next = outgoing
+                .get(&edges[current].to)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|&i| !used[i])
+                .min_by(|&i, &j| {
+                    let turn = |k: usize| {
+                        let (ex, ey) = direction(&edges[k]);
+                        (dx * ey - dy * ex).atan2(dx * ex + dy * ey)
+                    };
+                    turn(i).total_cmp(&turn(j))
+                });
+            let Some(next) = next else {
+                break;
+            };
+            used[next] = true;
+            ring.push(edges[next]);
+            current = next;
+        }
+        if edges[current].to == edges[start].from && ring.len() >= 3 {
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+/// Connect a hole to the surrounding ring by a cut of zero width between
+/// their closest vertices.
+fn bridge(ring: &mut Vec<Edge>, hole: &[Edge], plane: Plane) {
+    let distance = |a: &Coord, b: &Coord| {
+        let ((x1, y1), (x2, y2)) = (plane.point(a), plane.point(b));
+        (x2 - x1).hypot(y2 - y1)
+    };
+    let mut closest = (0, 0, f64::INFINITY);
+    for (i, outer) in ring.iter().enumerate() {
+        for (j, inner) in hole.iter().enumerate() {
+            let d = distance(&outer.from, &inner.from);
+            if d < closest.2 {
+                closest = (i, j, d);
+            }
+        }
+    }
+    let (i, j, _) = closest;
+    let (outer, inner) = (ring[i].from, hole[j].from);
+    let mut bridged = Vec::with_capacity(ring.len() + hole.len() + 2);
+    bridged.extend_from_slice(&ring[..i]);
+    bridged.push(Edge {
+        from: outer,
+        to: inner,
+        tag: None,
+    });
+    bridged.extend(hole[j..].iter().chain(&hole[..j]).copied());
+    bridged.push(Edge {
+        from: inner,
+        to: outer,
+        tag: None,
+    });
+    bridged.extend_from_slice(&ring[i..]);
+    *ring = bridged;
+}
+
+/// Convert a ring back to a geometry, replacing runs of edges from the same
+/// arc by an arc.
+fn restore_arcs(ring: &[Edge], sources: [&Geometry; 2]) -> Geometry {
+    if let Some(tag) = ring[0].tag
+        && ring.iter().all(|edge| edge.tag == Some(tag))
+        && let Geometry::Circle {
+            centerpoint,
+            radius,
+        } = sources[tag.source]
+    {
+        return Geometry::Circle {
+            centerpoint: *centerpoint,
+            radius: *radius,
+        };
+    }
+
+    // Start at the beginning of an arc or at a straight edge
+    let starts_run = |i: usize| {
+        let previous = ring[(i + ring.len() - 1) % ring.len()].tag;
+        ring[i].tag.is_none() || ring[i].tag != previous
+    };
+    let start = (0..ring.len()).find(|&i| starts_run(i)).unwrap_or(0);
+
+    let mut segments = Vec::new();
+    let mut i = 0;
+    while i < ring.len() {
+        let edge = &ring[(start + i) % ring.len()];
+        let Some(tag) = edge.tag else {
+            // The end of a preceding arc is implied by the arc
+            if !matches!(segments.last(), Some(PolygonSegment::Point(_)) | None) {
+                i += 1;
+                continue;
+            }
+            segments.push(PolygonSegment::Point(edge.from));
+            i += 1;
+            continue;
+        };
+        let mut end = i + 1;
+        while end < ring.len() && ring[(start + end) % ring.len()].tag == Some(tag) {
+            end += 1;
+        }
+        let to = ring[(start + end - 1) % ring.len()].to;
+        segments.push(arc(sources[tag.source], tag, edge.from, to));
+        i = end;
+    }
+    Geometry::Polygon { segments }
+}
+
+/// The part of an arc (or circle) of the source geometry between two points.
+fn arc(source: &Geometry, tag: Tag, from: Coord, to: Coord) -> PolygonSegment {
+    let direction = |direction: Direction| {
+        if tag.reversed {
+            direction.reversed()
+        } else {
+            direction
+        }
+    };
+    let bearing = |center: &Coord, point: &Coord| {
+        geometry::round_angle(geodesy::inverse(center, point).initial_bearing)
+    };
+    match source {
+        Geometry::Circle {
+            centerpoint,
+            radius,
+        } => PolygonSegment::ArcSegment(ArcSegment {
+            centerpoint: *centerpoint,
+            radius: *radius,
+            angle_start: bearing(centerpoint, &from),
+            angle_end: bearing(centerpoint, &to),
+            direction: direction(Direction::Cw),
+        }),
+        Geometry::Polygon { segments } => match &segments[tag.segment] {
+            PolygonSegment::ArcSegment(arc) => {
+                let Arc { start, end, .. } = arc.to_arc();
+                let (angle_start, angle_end) = match (tag.reversed, from, to) {
+                    (false, from, to) if from == start && to == end => {
+                        (arc.angle_start, arc.angle_end)
+                    }
+                    (true, from, to) if from == end && to == start => {
+                        (arc.angle_end, arc.angle_start)
+                    }
+                    _ => (
+                        bearing(&arc.centerpoint, &from),
+                        bearing(&arc.centerpoint, &to),
+                    ),
+                };
+                PolygonSegment::ArcSegment(ArcSegment {
+                    centerpoint: arc.centerpoint,
+                    radius: arc.radius,
+                    angle_start,
+                    angle_end,
+                    direction: direction(arc.direction),
+                })
+            }
+            PolygonSegment::Arc(arc) => PolygonSegment::Arc(Arc {
+                centerpoint: arc.centerpoint,
+                start: from,
+                end: to,
+                direction: direction(arc.direction),
+            }),
+            PolygonSegment::Point(point) => PolygonSegment::Point(*point),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    const NM: f64 = geodesy::METRES_PER_NM;
+
+    fn rectangle(north: f64, west: f64, south: f64, east: f64) -> Geometry {
+        Geometry::rectangle(
+            Coord::from_degrees(north, west),
+            Coord::from_degrees(south, east),
+        )
+    }
+
+    fn total_area(parts: &[Geometry]) -> f64 {
+        parts.iter().map(Geometry::area_m2).sum()
+    }
+
+    fn assert_close(actual: f64, expected: f64, relative: f64) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * relative,
+            "{actual} differs from {expected}"
+        );
+    }
+
+    #[test]
+    fn overlapping_rectangles() {
+        let a = rectangle(47.0, 8.0, 46.0, 9.0);
+        let b = rectangle(46.5, 8.5, 45.5, 9.5);
+
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.len(), 1);
+        assert_eq!(intersection[0].to_string(), "Polygon[4]");
+        let expected = rectangle(46.5, 8.5, 46.0, 9.0).area_m2();
+        assert_close(total_area(&intersection), expected, 1e-9);
+
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].to_string(), "Polygon[8]");
+        let expected = a.area_m2() + b.area_m2() - total_area(&intersection);
+        assert_close(total_area(&union), expected, 1e-9);
+
+        let difference = a.difference(&b);
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference[0].to_string(), "Polygon[6]");
+        let expected = a.area_m2() - total_area(&intersection);
+        assert_close(total_area(&difference), expected, 1e-9);
+    }
+
+    #[test]
+    fn disjoint_and_contained() {
+        let a = || rectangle(47.0, 8.0, 46.0, 9.0);
+        let b = || rectangle(45.0, 8.0, 44.0, 9.0);
+        assert_eq!(a().intersection(&b()), vec![]);
+        assert_eq!(a().union(&b()), vec![a(), b()]);
+        assert_eq!(a().difference(&b()), vec![a()]);
+
+        // A hole is connected by a cut
+        let inner = || rectangle(46.8, 8.2, 46.2, 8.8);
+        assert_eq!(a().intersection(&inner()), vec![inner()]);
+        assert_eq!(a().union(&inner()), vec![a()]);
+        assert_eq!(inner().difference(&a()), vec![]);
+        let difference = a().difference(&inner());
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference[0].to_string(), "Polygon[10]");
+        assert_close(
+            total_area(&difference),
+            a().area_m2() - inner().area_m2(),
+            1e-9,
+        );
+        assert!(!difference[0].contains(&Coord::from_degrees(46.5, 8.5)));
+        assert!(difference[0].contains(&Coord::from_degrees(46.1, 8.5)));
+    }
+
+    #[test]
+    fn circles() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let circle = Geometry::Circle {
+            centerpoint: center,
+            radius: 5.0,
+        };
+        // A rectangle covering the northern half
+        let north = rectangle(47.2, 7.7, 47.0, 8.3);
+
+        let parts = circle.difference(&north);
+        assert_eq!(parts.len(), 1);
+        let Geometry::Polygon { segments } = &parts[0] else {
+            panic!("{:?}", parts[0]);
+        };
+        let [PolygonSegment::ArcSegment(arc)] = &segments[..] else {
+            panic!("{segments:?}");
+        };
+        assert_eq!(arc.centerpoint, center);
+        assert_eq!(arc.radius, 5.0);
+        assert_eq!(arc.direction, Direction::Cw);
+        assert_close(total_area(&parts), PI * 25.0 * NM * NM / 2.0, 1e-3);
+
+        let parts = circle.intersection(&north);
+        assert_eq!(parts.len(), 1);
+        assert_close(total_area(&parts), PI * 25.0 * NM * NM / 2.0, 1e-3);
+
+        // A circle inside a polygon is kept
+        let big = rectangle(48.0, 7.0, 46.0, 9.0);
+        assert_eq!(
+            circle.intersection(&big),
+            vec![Geometry::Circle {
+                centerpoint: center,
+                radius: 5.0
+            }]
+        );
+    }
+
+    #[test]
+    fn adjacent_sectors() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let a = || Geometry::sector(center, 0.0, 5.0, 0.0, 90.0).unwrap();
+        let b = || Geometry::sector(center, 0.0, 5.0, 90.0, 180.0).unwrap();
+        let arc = |angle_start, angle_end| {
+            PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 5.0,
+                angle_start,
+                angle_end,
+                direction: Direction::Cw,
+            })
+        };
+        assert_eq!(
+            a().union(&b()),
+            vec![Geometry::Polygon {
+                segments: vec![
+                    PolygonSegment::Point(center),
+                    arc(0.0, 90.0),
+                    arc(90.0, 180.0),
+                ],
+            }]
+        );
+        assert_eq!(a().intersection(&b()), vec![]);
+        assert_eq!(a().difference(&b()), vec![a()]);
+    }
+}
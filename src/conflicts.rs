@@ -0,0 +1,223 @@
+use crate::{Airspace, Altitude, Geometry};
+
+/// Whether the vertical limits of two airspaces overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum VerticalOverlap {
+    /// The vertical limits overlap
+    Certain,
+    /// The vertical limits overlap depending on the ground elevation (for
+    /// `GND` and limits above ground) or on a limit that could not be parsed
+    Possible,
+}
+
+/// Two airspaces overlapping horizontally and vertically, see
+/// [`find_conflicts`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Overlap {
+    /// Index of the first airspace
+    pub first: usize,
+    /// Index of the second airspace, larger than `first`
+    pub second: usize,
+    /// Whether the vertical limits overlap for certain or only possibly
+    pub vertical: VerticalOverlap,
+    /// Area of the horizontal intersection in m²
+    pub area_m2: f64,
+}
+
+/// Two airspaces with identical geometries but different vertical limits,
+/// see [`find_conflicts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Duplicate {
+    /// Index of the first airspace
+    pub first: usize,
+    /// Index of the second airspace, larger than `first`
+    pub second: usize,
+}
+
+/// Result of [`find_conflicts`], ordered by airspace indices.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConflictReport {
+    /// Pairs of airspaces that overlap horizontally and vertically
+    pub overlaps: Vec<Overlap>,
+    /// Pairs of airspaces with identical geometries but different vertical
+    /// limits
+    pub duplicates: Vec<Duplicate>,
+}
+
+/// Find pairs of airspaces that overlap, and airspaces with identical
+/// geometries but different vertical limits.
+///
+/// Overlaps are reported if the vertical limits overlap (limits that only
+/// touch do not) and the horizontal intersection (see
+/// [`Geometry::intersection`]) covers at least `min_area_m2`, which skips
+/// slivers between neighbouring airspaces whose boundaries differ slightly.
+/// Flight levels are compared to altitudes at standard pressure. The
+/// activation times are ignored.
+///
+/// To compare two files, parse both into one list and only keep pairs
+/// across the files.
+///
+/// ```
+/// use openair::{Airspace, Altitude, Class, Coord, Geometry, VerticalOverlap, find_conflicts};
+///
+/// let airspace = |lower, upper, lat| Airspace {
+///     name: "Example Zone".to_string(),
+///     class: Class::D,
+///     type_: None,
+///     lower_bound: lower,
+///     upper_bound: upper,
+///     geom: Geometry::Circle {
+///         centerpoint: Coord::from_degrees(lat, 8.0),
+///         radius: 5.0,
+///     },
+///     frequency: None,
+///     call_sign: None,
+///     transponder_code: None,
+///     activation_times: None,
+///     label_points: vec![],
+///     extensions: vec![],
+/// };
+/// let airspaces = [
+///     airspace(Altitude::FeetAmsl(2000), Altitude::FlightLevel(100), 47.0),
+///     airspace(Altitude::FeetAmsl(4500), Altitude::FlightLevel(195), 47.1),
+///     airspace(Altitude::FlightLevel(100), Altitude::FlightLevel(195), 47.0),
+/// ];
+/// let report = find_conflicts(&airspaces, 1.0);
+/// assert_eq!(report.overlaps.len(), 2);
+/// assert_eq!((report.overlaps[0].first, report.overlaps[0].second), (0, 1));
+/// assert_eq!(report.overlaps[0].vertical, VerticalOverlap::Certain);
+/// assert_eq!((report.duplicates[0].first, report.duplicates[0].second), (0, 2));
+/// ```
+pub fn find_conflicts(airspaces: &[Airspace], min_area_m2: f64) -> ConflictReport {
+    let bboxes = airspaces.iter().map(Airspace::bbox).collect::<Vec<_>>();
+    let mut report = ConflictReport::default();
+    for (first, a) in airspaces.iter().enumerate() {
+        let Some(bbox) = &bboxes[first] else {
+            continue;
+        };
+        for (second, b) in airspaces.iter().enumerate().skip(first + 1) {
+            if !bboxes[second].is_some_and(|other| bbox.intersects(&other)) {
+                continue;
+            }
+            if a.geom == b.geom
+                && (a.lower_bound != b.lower_bound || a.upper_bound != b.upper_bound)
+            {
+                report.duplicates.push(Duplicate { first, second });
+            }
+            let Some(vertical) = vertical_overlap(a, b) else {
+                continue;
+            };
+            let area_m2 = a
+                .geom
+                .intersection(&b.geom)
+                .iter()
+                .map(Geometry::area_m2)
+                .sum::<f64>();
+            if area_m2 > 0.0 && area_m2 >= min_area_m2 {
+                report.overlaps.push(Overlap {
+                    first,
+                    second,
+                    vertical,
+                    area_m2,
+                });
+            }
+        }
+    }
+    report
+}
+
+fn vertical_overlap(a: &Airspace, b: &Airspace) -> Option<VerticalOverlap> {
+    match (
+        below(&a.lower_bound, &b.upper_bound),
+        below(&b.lower_bound, &a.upper_bound),
+    ) {
+        (Some(false), _) | (_, Some(false)) => None,
+        (Some(true), Some(true)) => Some(VerticalOverlap::Certain),
+        _ => Some(VerticalOverlap::Possible),
+    }
+}
+
+/// Returns true if the lower limit lies below the upper limit, or `None` if
+/// that depends on the ground elevation or the limits cannot be compared.
+fn below(lower: &Altitude, upper: &Altitude) -> Option<bool> {
+    if let (Some(lower), Some(upper)) = (above_ground(lower), above_ground(upper)) {
+        return Some(lower < upper);
+    }
+    if *upper == Altitude::Unlimited && above_ground(lower).is_some() {
+        return Some(true);
+    }
+    let (lower_min, lower_max) = range(lower);
+    let (upper_min, upper_max) = range(upper);
+    if lower_max < upper_min {
+        Some(true)
+    } else if lower_min >= upper_max {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Height above ground in feet, for limits relative to the ground.
+fn above_ground(altitude: &Altitude) -> Option<i32> {
+    match altitude {
+        Altitude::Gnd => Some(0),
+        Altitude::FeetAgl(ft) => Some(*ft),
+        _ => None,
+    }
+}
+
+/// Range of the altitude of a limit in feet AMSL, assuming that the ground
+/// is not below sea level.
+fn range(altitude: &Altitude) -> (f64, f64) {
+    match altitude {
+        Altitude::Gnd => (0.0, f64::INFINITY),
+        Altitude::FeetAmsl(ft) => (f64::from(*ft), f64::from(*ft)),
+        Altitude::FeetAgl(ft) => (f64::from(*ft), f64::INFINITY),
+        Altitude::FlightLevel(fl) => (f64::from(*fl) * 100.0, f64::from(*fl) * 100.0),
+        Altitude::Unlimited => (f64::INFINITY, f64::INFINITY),
+        Altitude::Other(_) => (f64::NEG_INFINITY, f64::INFINITY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertical_limits() {
+        use Altitude::*;
+
+        assert_eq!(below(&FeetAmsl(1000), &FeetAmsl(2000)), Some(true));
+        assert_eq!(below(&FeetAmsl(4500), &FlightLevel(100)), Some(true));
+        assert_eq!(below(&FlightLevel(100), &FeetAmsl(10000)), Some(false));
+        assert_eq!(below(&FeetAmsl(2000), &Unlimited), Some(true));
+        assert_eq!(below(&Unlimited, &Unlimited), Some(false));
+
+        // Limits above ground are compared directly to each other
+        assert_eq!(below(&FeetAgl(500), &FeetAgl(1000)), Some(true));
+        assert_eq!(below(&FeetAgl(1000), &FeetAgl(1000)), Some(false));
+        assert_eq!(below(&Gnd, &FeetAgl(1000)), Some(true));
+        assert_eq!(below(&Gnd, &FeetAgl(0)), Some(false));
+        assert_eq!(below(&FeetAgl(0), &Gnd), Some(false));
+        assert_eq!(below(&Gnd, &Unlimited), Some(true));
+        assert_eq!(below(&FeetAgl(1000), &Unlimited), Some(true));
+
+        // The ground lies somewhere at or above sea level
+        assert_eq!(below(&Gnd, &FeetAmsl(1000)), None);
+        assert_eq!(below(&FeetAmsl(1000), &Gnd), None);
+        assert_eq!(below(&Gnd, &FlightLevel(100)), None);
+
+        // ... and to altitudes above sea level if the ground elevation does
+        // not matter
+        assert_eq!(below(&FeetAgl(1000), &FeetAmsl(4500)), None);
+        assert_eq!(below(&FeetAgl(1000), &FeetAmsl(500)), Some(false));
+        assert_eq!(below(&FeetAmsl(500), &FeetAgl(1000)), Some(true));
+        assert_eq!(below(&FeetAmsl(4500), &FeetAgl(1000)), None);
+        assert_eq!(below(&Other("NOTAM".to_string()), &FlightLevel(100)), None);
+    }
+}
@@ -0,0 +1,583 @@
+use crate::{ArcSegment, Coord, Direction, Geometry, Orientation, PolygonSegment, geodesy};
+
+/// Turns (in degrees) smaller than this are treated as straight.
+const STRAIGHT: f64 = 1e-6;
+
+/// Concave joins turning more than this (in degrees) are not mitered.
+const MITER_LIMIT: f64 = 120.0;
+
+impl Geometry {
+    /// A sector between two radii in nautical miles around a centerpoint,
+    /// running clockwise from `angle_start` to `angle_end` (bearings in
+    /// degrees).
+    ///
+    /// With an inner radius of zero the sector is a pie slice, or a circle
+    /// if both angles are equal. A ring (an inner radius above zero with
+    /// equal angles) cannot be represented by a single polygon and results
+    /// in an error, as does an outer radius not above the inner one.
+    ///
+    /// ```
+    /// use openair::{Coord, Geometry};
+    ///
+    /// let sector = Geometry::sector(Coord::from_degrees(47.0, 8.0), 2.0, 5.0, 270.0, 90.0).unwrap();
+    /// assert_eq!(sector.to_string(), "Polygon[2]");
+    /// ```
+    pub fn sector(
+        centerpoint: Coord,
+        inner_radius_nm: f32,
+        outer_radius_nm: f32,
+        angle_start: f32,
+        angle_end: f32,
+    ) -> Result<Self, String> {
+        if !(inner_radius_nm >= 0.0 && outer_radius_nm > inner_radius_nm) {
+            return Err(format!(
+                "Invalid sector radii: {inner_radius_nm} and {outer_radius_nm}"
+            ));
+        }
+        let full_circle = angle_start.rem_euclid(360.0) == angle_end.rem_euclid(360.0);
+        if full_circle && inner_radius_nm > 0.0 {
+            return Err(format!(
+                "Invalid sector with an inner radius and equal angles: {angle_start} and {angle_end}"
+            ));
+        }
+        if full_circle {
+            return Ok(Self::Circle {
+                centerpoint,
+                radius: outer_radius_nm,
+            });
+        }
+        let outer = PolygonSegment::ArcSegment(ArcSegment {
+            centerpoint,
+            radius: outer_radius_nm,
+            angle_start,
+            angle_end,
+            direction: Direction::Cw,
+        });
+        let segments = if inner_radius_nm > 0.0 {
+            let inner = PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint,
+                radius: inner_radius_nm,
+                angle_start: angle_end,
+                angle_end: angle_start,
+                direction: Direction::Ccw,
+            });
+            vec![outer, inner]
+        } else {
+            vec![PolygonSegment::Point(centerpoint), outer]
+        };
+        Ok(Self::Polygon { segments })
+    }
+
+    /// A rectangle along parallels and meridians with two opposite corners.
+    ///
+    /// The rectangle spans the shorter way around in longitude, so it may
+    /// cross the antimeridian. Its points run clockwise, starting at the
+    /// north-western corner.
+    pub fn rectangle(corner: Coord, opposite: Coord) -> Self {
+        let (north, south) = if corner.lat() >= opposite.lat() {
+            (corner.lat(), opposite.lat())
+        } else {
+            (opposite.lat(), corner.lat())
+        };
+        let (west, east) = if geodesy::normalize_longitude(opposite.lng() - corner.lng()) >= 0.0 {
+            (corner.lng(), opposite.lng())
+        } else {
+            (opposite.lng(), corner.lng())
+        };
+        let point = |lat, lng| PolygonSegment::Point(Coord::from_degrees(lat, lng));
+        Self::Polygon {
+            segments: vec![
+                point(north, west),
+                point(north, east),
+                point(south, east),
+                point(south, west),
+            ],
+        }
+    }
+
+    /// A corridor of the given total width in metres along a polyline.
+    ///
+    /// The sides are offset geodesically from the polyline, with arcs around
+    /// the vertices on the outside of turns and around both ends. A single
+    /// point results in a circle. Sharp turns in a wide corridor may result
+    /// in self-intersections (see [`Self::validate`]). The radius and angles
+    /// of the arcs are rounded to 0.001 NM and 0.01°.
+    ///
+    /// ```
+    /// use openair::{Coord, Geometry};
+    ///
+    /// let points = [Coord::from_degrees(47.0, 8.0), Coord::from_degrees(47.0, 8.5)];
+    /// let corridor = Geometry::corridor(&points, 1852.0).unwrap();
+    /// assert_eq!(corridor.to_string(), "Polygon[2]");
+    /// ```
+    pub fn corridor(points: &[Coord], width_m: f64) -> Result<Self, String> {
+        if width_m.is_nan() || width_m <= 0.0 {
+            return Err(format!("Invalid corridor width: {width_m}"));
+        }
+        let mut points = points.to_vec();
+        points.dedup();
+        let Some(first) = points.first() else {
+            return Err("Corridor needs at least one point".to_string());
+        };
+        if points.len() == 1 {
+            return Ok(circle(*first, width_m / 2.0));
+        }
+
+        // Run along the polyline and back, with the corridor to the right
+        let forward = points
+            .windows(2)
+            .map(|pair| Element::line(pair[0], pair[1]));
+        let backward = points
+            .windows(2)
+            .rev()
+            .map(|pair| Element::line(pair[1], pair[0]));
+        let elements = forward.chain(backward).collect::<Vec<_>>();
+        Ok(Self::Polygon {
+            segments: offset(&elements, width_m / 2.0),
+        })
+    }
+
+    /// Grow the geometry by the given distance in metres.
+    ///
+    /// Circles grow their radius. Polygon edges are offset geodesically,
+    /// with arcs around convex corners, and arcs keep their centerpoint.
+    /// Concave arcs with a radius below the distance shrink to their
+    /// centerpoint. The result runs clockwise. Concave parts narrower than
+    /// twice the distance may result in self-intersections (see
+    /// [`Self::validate`]). The radius and angles of new and grown arcs are
+    /// rounded to 0.001 NM and 0.01°.
+    ///
+    /// ```
+    /// use openair::{Coord, Geometry};
+    ///
+    /// let square = Geometry::rectangle(Coord::from_degrees(47.0, 8.0), Coord::from_degrees(46.9, 8.1));
+    /// let buffer = square.buffer(500.0).unwrap();
+    /// assert_eq!(buffer.to_string(), "Polygon[4]");
+    /// assert!(buffer.area_m2() > square.area_m2());
+    /// ```
+    pub fn buffer(&self, distance_m: f64) -> Result<Self, String> {
+        if distance_m.is_nan() || distance_m <= 0.0 {
+            return Err(format!("Invalid buffer distance: {distance_m}"));
+        }
+        let segments = match self {
+            Self::Circle {
+                centerpoint,
+                radius,
+            } => {
+                return Ok(Self::Circle {
+                    centerpoint: *centerpoint,
+                    radius: radius + (distance_m / geodesy::METRES_PER_NM) as f32,
+                });
+            }
+            Self::Polygon { segments } => segments,
+        };
+        let mut elements = elements(segments);
+        if elements.is_empty() {
+            return match segments.first() {
+                Some(segment) => Ok(circle(segment.start_point(), distance_m)),
+                None => Err("Could not buffer an empty polygon".to_string()),
+            };
+        }
+        if self.orientation() == Some(Orientation::Counterclockwise) {
+            elements.reverse();
+            elements.iter_mut().for_each(Element::reverse);
+        }
+        Ok(Self::Polygon {
+            segments: offset(&elements, distance_m),
+        })
+    }
+}
+
+/// A circle with a radius in metres.
+fn circle(centerpoint: Coord, radius: f64) -> Geometry {
+    Geometry::Circle {
+        centerpoint,
+        radius: (radius / geodesy::METRES_PER_NM) as f32,
+    }
+}
+
+/// A piece of a polygon boundary.
+enum Element {
+    Line {
+        from: Coord,
+        to: Coord,
+    },
+    /// An arc with a radius in metres and bearings in degrees
+    Arc {
+        center: Coord,
+        radius: f64,
+        start: f64,
+        end: f64,
+        direction: Direction,
+    },
+}
+
+impl Element {
+    fn line(from: Coord, to: Coord) -> Self {
+        Self::Line { from, to }
+    }
+
+    fn start(&self) -> Coord {
+        match self {
+            Self::Line { from, .. } => *from,
+            Self::Arc {
+                center,
+                radius,
+                start,
+                ..
+            } => geodesy::direct(center, *start, *radius),
+        }
+    }
+
+    fn end(&self) -> Coord {
+        match self {
+            Self::Line { to, .. } => *to,
+            Self::Arc {
+                center,
+                radius,
+                end,
+                ..
+            } => geodesy::direct(center, *end, *radius),
+        }
+    }
+
+    /// Direction of travel at the start, as a bearing in degrees.
+    fn start_tangent(&self) -> f64 {
+        match self {
+            Self::Line { from, to } => geodesy::inverse(from, to).initial_bearing,
+            Self::Arc {
+                center, direction, ..
+            } => arc_tangent(center, &self.start(), *direction),
+        }
+    }
+
+    /// Direction of travel at the end, as a bearing in degrees.
+    fn end_tangent(&self) -> f64 {
+        match self {
+            Self::Line { from, to } => geodesy::inverse(from, to).final_bearing,
+            Self::Arc {
+                center, direction, ..
+            } => arc_tangent(center, &self.end(), *direction),
+        }
+    }
+
+    fn reverse(&mut self) {
+        match self {
+            Self::Line { from, to } => std::mem::swap(from, to),
+            Self::Arc {
+                start,
+                end,
+                direction,
+                ..
+            } => {
+                std::mem::swap(start, end);
+                *direction = direction.reversed();
+            }
+        }
+    }
+}
+
+/// Direction of travel along an arc at the given point.
+fn arc_tangent(center: &Coord, point: &Coord, direction: Direction) -> f64 {
+    // Bearing away from the center at the point
+    let bearing = geodesy::inverse(center, point).final_bearing;
+    match direction {
+        Direction::Cw => bearing + 90.0,
+        Direction::Ccw => bearing - 90.0,
+    }
+}
+
+/// The boundary of a polygon as lines and arcs, closed by a line to the
+/// first point if needed.
+fn elements(segments: &[PolygonSegment]) -> Vec<Element> {
+    let mut elements = Vec::new();
+    let mut first = None;
+    let mut current: Option<Coord> = None;
+    for segment in segments {
+        let arc = match segment {
+            PolygonSegment::Point(_) => None,
+            PolygonSegment::ArcSegment(arc) => Some(arc_element(arc)),
+            PolygonSegment::Arc(arc) => Some(arc_element(&arc.to_arc_segment())),
+        };
+        let start = arc
+            .as_ref()
+            .map_or_else(|| segment.start_point(), Element::start);
+        if let Some(from) = current
+            && from != start
+        {
+            elements.push(Element::line(from, start));
+        }
+        first.get_or_insert(start);
+        current = Some(arc.as_ref().map_or(start, Element::end));
+        elements.extend(arc);
+    }
+    if let (Some(from), Some(to)) = (current, first)
+        && from != to
+    {
+        elements.push(Element::line(from, to));
+    }
+    elements
+}
+
+fn arc_element(arc: &ArcSegment) -> Element {
+    Element::Arc {
+        center: arc.centerpoint,
+        radius: f64::from(arc.radius) * geodesy::METRES_PER_NM,
+        start: f64::from(arc.angle_start),
+        end: f64::from(arc.angle_end),
+        direction: arc.direction,
+    }
+}
+
+/// Offset a closed boundary to the left (the outside of a clockwise
+/// polygon) by the given distance in metres.
+fn offset(elements: &[Element], distance: f64) -> Vec<PolygonSegment> {
+    let radius = (distance / geodesy::METRES_PER_NM) as f32;
+    let shift = |point: &Coord, tangent: f64| geodesy::direct(point, tangent - 90.0, distance);
+    let mut segments = Vec::new();
+    for (i, element) in elements.iter().enumerate() {
+        let previous = &elements[(i + elements.len() - 1) % elements.len()];
+        let vertex = element.start();
+        let (t_in, t_out) = (previous.end_tangent(), element.start_tangent());
+        // Turn to the right in (-180, 180] degrees, treating turns around
+        // the end of a corridor as right turns despite rounding
+        let turn = 180.0 - geodesy::normalize_bearing(180.0 - (t_out - t_in));
+        let turn = if turn < STRAIGHT - 180.0 {
+            turn + 360.0
+        } else {
+            turn
+        };
+        let is_line = |element: &Element| matches!(element, Element::Line { .. });
+
+        if turn > STRAIGHT {
+            // Convex corner, or the end of a corridor
+            segments.push(PolygonSegment::ArcSegment(
+                ArcSegment {
+                    centerpoint: vertex,
+                    radius,
+                    angle_start: geodesy::normalize_bearing(t_in - 90.0) as f32,
+                    angle_end: geodesy::normalize_bearing(t_out - 90.0) as f32,
+                    direction: Direction::Cw,
+                }
+                .rounded(),
+            ));
+        } else if turn < -STRAIGHT && turn > -MITER_LIMIT && is_line(previous) && is_line(element) {
+            // Concave corner between lines, join the sides where they meet
+            let bearing = t_in - 90.0 + turn / 2.0;
+            let miter = distance / (turn / 2.0).to_radians().cos();
+            segments.push(PolygonSegment::Point(geodesy::direct(
+                &vertex, bearing, miter,
+            )));
+        } else {
+            if turn < -STRAIGHT && is_line(previous) {
+                segments.push(PolygonSegment::Point(shift(&previous.end(), t_in)));
+            }
+            if is_line(element) {
+                segments.push(PolygonSegment::Point(shift(&vertex, t_out)));
+            }
+        }
+
+        if let Element::Arc {
+            center,
+            radius,
+            start,
+            end,
+            direction,
+        } = element
+        {
+            let radius = match direction {
+                Direction::Cw => radius + distance,
+                Direction::Ccw => radius - distance,
+            };
+            segments.push(if radius > 0.0 {
+                PolygonSegment::ArcSegment(
+                    ArcSegment {
+                        centerpoint: *center,
+                        radius: (radius / geodesy::METRES_PER_NM) as f32,
+                        angle_start: *start as f32,
+                        angle_end: *end as f32,
+                        direction: *direction,
+                    }
+                    .rounded(),
+                )
+            } else {
+                PolygonSegment::Point(*center)
+            });
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    const NM: f64 = geodesy::METRES_PER_NM;
+
+    fn assert_close(actual: f64, expected: f64, relative: f64) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * relative,
+            "{actual} differs from {expected}"
+        );
+    }
+
+    #[test]
+    fn sector() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let geom = Geometry::sector(center, 2.0, 5.0, 270.0, 90.0).unwrap();
+        assert_eq!(geom.validate(), vec![]);
+        assert_close(geom.area_m2(), PI * (25.0 - 4.0) * NM * NM / 2.0, 1e-3);
+        assert!(geom.contains(&center.destination(0.0, 3.0 * NM)));
+        assert!(!geom.contains(&center.destination(180.0, 3.0 * NM)));
+        assert!(!geom.contains(&center));
+
+        let pie = Geometry::sector(center, 0.0, 5.0, 0.0, 90.0).unwrap();
+        assert_close(pie.area_m2(), PI * 25.0 * NM * NM / 4.0, 1e-3);
+        assert!(pie.contains(&center.destination(45.0, 1.0)));
+
+        assert_eq!(
+            Geometry::sector(center, 0.0, 5.0, 90.0, 450.0).unwrap(),
+            Geometry::Circle {
+                centerpoint: center,
+                radius: 5.0
+            }
+        );
+
+        // Rings and invalid radii are rejected
+        assert!(Geometry::sector(center, 2.0, 5.0, 90.0, 450.0).is_err());
+        assert!(Geometry::sector(center, 5.0, 2.0, 0.0, 90.0).is_err());
+        assert!(Geometry::sector(center, -1.0, 5.0, 0.0, 90.0).is_err());
+    }
+
+    #[test]
+    fn rectangle() {
+        let expected = Geometry::rectangle(
+            Coord::from_degrees(47.0, 8.0),
+            Coord::from_degrees(46.0, 9.0),
+        );
+        assert_eq!(expected.orientation(), Some(Orientation::Clockwise));
+        for (corner, opposite) in [((46.0, 8.0), (47.0, 9.0)), ((46.0, 9.0), (47.0, 8.0))] {
+            let geom = Geometry::rectangle(
+                Coord::from_degrees(corner.0, corner.1),
+                Coord::from_degrees(opposite.0, opposite.1),
+            );
+            assert_eq!(geom, expected);
+        }
+
+        // Across the antimeridian
+        let geom = Geometry::rectangle(
+            Coord::from_degrees(10.0, -170.0),
+            Coord::from_degrees(-10.0, 170.0),
+        );
+        assert!(geom.contains(&Coord::from_degrees(0.0, 180.0)));
+        assert!(!geom.contains(&Coord::from_degrees(0.0, 0.0)));
+    }
+
+    #[test]
+    fn corridor() {
+        let start = Coord::from_degrees(47.0, 8.0);
+        let end = start.destination(90.0, 10_000.0);
+        let geom = Geometry::corridor(&[start, end], 2000.0).unwrap();
+        assert_eq!(geom.validate(), vec![]);
+        assert_close(
+            geom.area_m2(),
+            10_000.0 * 2000.0 + PI * 1000.0 * 1000.0,
+            1e-3,
+        );
+
+        // An L-shaped corridor
+        let corner = end.destination(180.0, 10_000.0);
+        let geom = Geometry::corridor(&[start, end, corner], 2000.0).unwrap();
+        assert_eq!(geom.validate(), vec![]);
+        assert_eq!(geom.orientation(), Some(Orientation::Clockwise));
+        // Two straight parts, minus the overlap at the inner corner, plus
+        // the quarter circle at the outer corner and the round ends
+        let expected = 2.0 * 10_000.0 * 2000.0 - 1000.0 * 1000.0 + PI * 1000.0 * 1000.0 * 1.25;
+        assert_close(geom.area_m2(), expected, 1e-3);
+        assert!(geom.contains(&end.destination(45.0, 1200.0)));
+        assert!(!geom.contains(&end.destination(225.0, 1200.0)));
+
+        assert_eq!(
+            Geometry::corridor(&[start, start], 2000.0),
+            Ok(Geometry::Circle {
+                centerpoint: start,
+                radius: (1000.0 / NM) as f32
+            })
+        );
+        assert!(Geometry::corridor(&[], 2000.0).is_err());
+        assert!(Geometry::corridor(&[start, end], 0.0).is_err());
+    }
+
+    #[test]
+    fn buffer() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let circle = Geometry::Circle {
+            centerpoint: center,
+            radius: 5.0,
+        };
+        assert_eq!(
+            circle.buffer(NM),
+            Ok(Geometry::Circle {
+                centerpoint: center,
+                radius: 6.0
+            })
+        );
+
+        // Both orientations grow the same way
+        let square = Geometry::rectangle(center, center.destination(135.0, 10_000.0));
+        let area = square.area_m2();
+        let perimeter = square.perimeter_m();
+        let expected = area + perimeter * 500.0 + PI * 500.0 * 500.0;
+        let mut reversed = Geometry::rectangle(center, center.destination(135.0, 10_000.0));
+        reversed.normalize(&crate::NormalizeOptions {
+            orientation: Some(Orientation::Counterclockwise),
+            ..Default::default()
+        });
+        for geom in [square, reversed] {
+            let buffer = geom.buffer(500.0).unwrap();
+            assert_eq!(buffer.validate(), vec![]);
+            assert_close(buffer.area_m2(), expected, 1e-3);
+        }
+
+        // Arcs grow around their centerpoint
+        let pie = Geometry::sector(center, 0.0, 5.0, 0.0, 90.0).unwrap();
+        let buffer = pie.buffer(NM).unwrap();
+        assert_eq!(buffer.validate(), vec![]);
+        let Geometry::Polygon { segments } = &buffer else {
+            panic!();
+        };
+        assert!(segments.contains(&PolygonSegment::ArcSegment(ArcSegment {
+            centerpoint: center,
+            radius: 6.0,
+            angle_start: 0.0,
+            angle_end: 90.0,
+            direction: Direction::Cw,
+        })));
+        let ring = Geometry::sector(center, 2.0, 5.0, 0.0, 90.0).unwrap();
+        let buffer = ring.buffer(NM).unwrap();
+        let Geometry::Polygon { segments } = &buffer else {
+            panic!();
+        };
+        assert!(segments.contains(&PolygonSegment::ArcSegment(ArcSegment {
+            centerpoint: center,
+            radius: 1.0,
+            angle_start: 90.0,
+            angle_end: 0.0,
+            direction: Direction::Ccw,
+        })));
+
+        let point = Geometry::Polygon {
+            segments: vec![PolygonSegment::Point(center)],
+        };
+        assert_eq!(
+            point.buffer(NM),
+            Ok(Geometry::Circle {
+                centerpoint: center,
+                radius: 1.0
+            })
+        );
+        assert!(circle.buffer(-1.0).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::Altitude;
+
+/// Standard pressure in hPa.
+const STANDARD_PRESSURE: f64 = 1013.25;
+
+/// Additional information needed to resolve the vertical limits of an
+/// airspace at a certain position (see [`Airspace::contains_position`]).
+///
+/// [`Airspace::contains_position`]: crate::Airspace::contains_position
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PositionContext {
+    /// QNH in hPa, used to convert flight levels to altitudes. Standard
+    /// pressure (1013.25 hPa) is assumed if not set.
+    pub qnh: Option<f64>,
+    /// Ground elevation at the position in feet AMSL. Required for AGL
+    /// limits. Without it, the position is assumed to be above `GND`.
+    pub ground_elevation: Option<f64>,
+    /// Evaluation time for the activation times. Activation times are
+    /// ignored if not set.
+    pub time: Option<iso8601::DateTime>,
+}
+
+/// One of the vertical limits of an airspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// The lower limit (`AL`)
+    Lower,
+    /// The upper limit (`AH`)
+    Upper,
+}
+
+/// Position relative to an airspace.
+///
+/// Vertical distances (`margin`) are in feet and may be infinite for `GND`
+/// without known ground elevation or unlimited upper bounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Containment {
+    /// Inside the airspace. `nearest` is the closer vertical limit, `margin`
+    /// the distance to it.
+    Inside { nearest: Limit, margin: f64 },
+    /// Below the lower limit by `margin`
+    Below { margin: f64 },
+    /// Above the upper limit by `margin`
+    Above { margin: f64 },
+    /// Outside the lateral limits
+    OutsideHorizontally,
+    /// Within the lateral limits, but the airspace is not active at the
+    /// evaluation time
+    Inactive,
+}
+
+impl Containment {
+    /// Returns true if the position is inside the airspace.
+    pub fn is_inside(&self) -> bool {
+        matches!(self, Self::Inside { .. })
+    }
+
+    /// Returns the vertical limit that decided the result, if any.
+    pub fn decisive_limit(&self) -> Option<Limit> {
+        match self {
+            Self::Inside { nearest, .. } => Some(*nearest),
+            Self::Below { .. } => Some(Limit::Lower),
+            Self::Above { .. } => Some(Limit::Upper),
+            Self::OutsideHorizontally | Self::Inactive => None,
+        }
+    }
+}
+
+/// Resolve a vertical limit to feet AMSL.
+pub(crate) fn resolve_altitude(
+    altitude: &Altitude,
+    context: &PositionContext,
+) -> Result<f64, String> {
+    match altitude {
+        Altitude::Gnd => Ok(context.ground_elevation.unwrap_or(f64::NEG_INFINITY)),
+        Altitude::FeetAmsl(ft) => Ok(f64::from(*ft)),
+        Altitude::FeetAgl(ft) => context
+            .ground_elevation
+            .map(|ground| ground + f64::from(*ft))
+            .ok_or_else(|| format!("Ground elevation required for altitude {altitude}")),
+        Altitude::FlightLevel(fl) => {
+            let qnh = context.qnh.unwrap_or(STANDARD_PRESSURE);
+            Ok(f64::from(*fl) * 100.0 - pressure_altitude(qnh))
+        }
+        Altitude::Unlimited => Ok(f64::INFINITY),
+        Altitude::Other(_) => Err(format!("Cannot resolve altitude {altitude}")),
+    }
+}
+
+/// Pressure altitude in feet of the given pressure in the ICAO standard
+/// atmosphere.
+fn pressure_altitude(hpa: f64) -> f64 {
+    145_366.45 * (1.0 - (hpa / STANDARD_PRESSURE).powf(0.190_284))
+}
+
+/// Compare an altitude against resolved lower and upper limits.
+pub(crate) fn vertical_containment(altitude: f64, lower: f64, upper: f64) -> Containment {
+    if altitude < lower {
+        Containment::Below {
+            margin: lower - altitude,
+        }
+    } else if altitude > upper {
+        Containment::Above {
+            margin: altitude - upper,
+        }
+    } else if altitude - lower <= upper - altitude {
+        Containment::Inside {
+            nearest: Limit::Lower,
+            margin: altitude - lower,
+        }
+    } else {
+        Containment::Inside {
+            nearest: Limit::Upper,
+            margin: upper - altitude,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let context = PositionContext {
+            qnh: Some(1023.0),
+            ground_elevation: Some(1500.0),
+            time: None,
+        };
+        let resolve = |altitude| resolve_altitude(&altitude, &context);
+        assert_eq!(resolve(Altitude::Gnd), Ok(1500.0));
+        assert_eq!(resolve(Altitude::FeetAmsl(4500)), Ok(4500.0));
+        assert_eq!(resolve(Altitude::FeetAgl(1000)), Ok(2500.0));
+        assert_eq!(resolve(Altitude::Unlimited), Ok(f64::INFINITY));
+        // About 27 ft per hPa near sea level
+        let fl100 = resolve(Altitude::FlightLevel(100)).unwrap();
+        assert!((fl100 - 10_266.0).abs() < 1.0, "{fl100}");
+        assert_eq!(
+            resolve(Altitude::Other("NOTAM".to_string())),
+            Err("Cannot resolve altitude ?(NOTAM)".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_without_context() {
+        let context = PositionContext::default();
+        let resolve = |altitude| resolve_altitude(&altitude, &context);
+        assert_eq!(resolve(Altitude::Gnd), Ok(f64::NEG_INFINITY));
+        assert_eq!(resolve(Altitude::FlightLevel(100)), Ok(10_000.0));
+        assert_eq!(
+            resolve(Altitude::FeetAgl(1000)),
+            Err("Ground elevation required for altitude 1000 ft AGL".to_string())
+        );
+    }
+
+    #[test]
+    fn vertical() {
+        assert_eq!(
+            vertical_containment(900.0, 1000.0, 5000.0),
+            Containment::Below { margin: 100.0 }
+        );
+        assert_eq!(
+            vertical_containment(5500.0, 1000.0, 5000.0),
+            Containment::Above { margin: 500.0 }
+        );
+        assert_eq!(
+            vertical_containment(1200.0, 1000.0, 5000.0),
+            Containment::Inside {
+                nearest: Limit::Lower,
+                margin: 200.0
+            }
+        );
+        assert_eq!(
+            vertical_containment(4000.0, f64::NEG_INFINITY, 5000.0),
+            Containment::Inside {
+                nearest: Limit::Upper,
+                margin: 1000.0
+            }
+        );
+    }
+}
@@ -1,64 +1,327 @@
-use std::io::Write;
+use std::{fmt, io::Write};
+
+use crate::{
+    geodesy,
+    writer::{CoordStyle, WriterOptions},
+};
+
+/// Milli-arc-seconds per degree.
+const MAS_PER_DEGREE: f64 = 3_600_000.0;
 
 /// A coordinate pair (WGS84).
-#[derive(Debug, PartialEq, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+///
+/// Latitude and longitude are stored as integer milli-arc-seconds (about 3 cm
+/// at the equator). This makes coordinates exactly comparable and hashable,
+/// and allows writing them without loss of precision.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Coord {
-    pub lat: f64,
-    pub lng: f64,
+    lat: i32,
+    lng: i32,
+}
+
+impl fmt::Debug for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Coord")
+            .field("lat", &self.lat())
+            .field("lng", &self.lng())
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Coord {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Coord", 2)?;
+        state.serialize_field("lat", &self.lat())?;
+        state.serialize_field("lng", &self.lng())?;
+        state.end()
+    }
+}
+
+/// A coordinate notation accepted by [`Coord::parse_as`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoordFormat {
+    /// OpenAir notation: `46:51:44 N 009:19:42 E` (optionally with fractional
+    /// seconds) or `46:51.44 N 009:19.42 E`
+    OpenAir,
+    /// Compact notation without separators: `473015N 0083020E` or
+    /// `4730.25N 00830.33E`
+    Compact,
+    /// Decimal degrees, either with hemisphere (`47.5042N 8.5056E`) or signed
+    /// (`47.5042, 8.5056`)
+    Decimal,
+    /// Hemisphere letter first: `N47 30.25 E008 30.33` or `N47:30:15 E8:30:20`
+    HemisphereFirst,
+    /// Degree, minute and second symbols: `47°30'15"N 8°30'20"E` or
+    /// `N47°30.25' E8°30.33'`
+    Symbols,
+}
+
+impl CoordFormat {
+    /// All supported notations.
+    pub const ALL: [CoordFormat; 5] = [
+        Self::OpenAir,
+        Self::Compact,
+        Self::Decimal,
+        Self::HemisphereFirst,
+        Self::Symbols,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::OpenAir => "OpenAir",
+            Self::Compact => "compact",
+            Self::Decimal => "decimal",
+            Self::HemisphereFirst => "hemisphere-first",
+            Self::Symbols => "symbol",
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<Coord, ()> {
+        match self {
+            Self::OpenAir => parse_openair(input),
+            Self::Compact => parse_pair(input, compact_component),
+            Self::Decimal => parse_pair(input, decimal_hemisphere_component)
+                .or_else(|_| parse_pair(input, decimal_signed_component)),
+            Self::HemisphereFirst => parse_pair(input, hemisphere_first_component),
+            Self::Symbols => parse_pair(input, symbol_component),
+        }
+    }
 }
 
 impl Coord {
-    pub fn parse(data: &str) -> Result<Self, String> {
-        let input = data.trim();
-        let err = || format!("Invalid coord: \"{data}\"");
+    /// Create a coordinate from decimal degrees, rounded to the nearest
+    /// milli-arc-second.
+    ///
+    /// The latitude must lie within ±90° and the longitude within ±180°.
+    /// This is only checked in debug builds, use [`Self::try_from_degrees`]
+    /// for untrusted input. Values outside of these ranges produce invalid
+    /// coordinates in release builds.
+    pub fn from_degrees(lat: f64, lng: f64) -> Self {
+        debug_assert!(
+            (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng),
+            "Coordinate out of range: {lat}, {lng}"
+        );
+        Self {
+            lat: (lat * MAS_PER_DEGREE).round() as i32,
+            lng: (lng * MAS_PER_DEGREE).round() as i32,
+        }
+    }
 
-        // Parse latitude coordinate and direction
-        let (mut lat, rest) = parse_coord_component(input, true).map_err(|_| err())?;
-        let (lat_is_negative, rest) = parse_direction(rest, true).map_err(|_| err())?;
-        if lat_is_negative {
-            lat = -lat;
+    /// Create a coordinate from decimal degrees, failing if the latitude is
+    /// not within ±90° or the longitude not within ±180°.
+    pub fn try_from_degrees(lat: f64, lng: f64) -> Result<Self, String> {
+        if !(-90.0..=90.0).contains(&lat) {
+            return Err(format!("Latitude out of range: {lat}"));
+        }
+        if !(-180.0..=180.0).contains(&lng) {
+            return Err(format!("Longitude out of range: {lng}"));
         }
+        Ok(Self::from_degrees(lat, lng))
+    }
+
+    /// Create a coordinate from milli-arc-seconds.
+    pub fn from_mas(lat: i32, lng: i32) -> Self {
+        Self { lat, lng }
+    }
+
+    /// Returns the latitude in decimal degrees.
+    pub fn lat(&self) -> f64 {
+        f64::from(self.lat) / MAS_PER_DEGREE
+    }
+
+    /// Returns the longitude in decimal degrees.
+    pub fn lng(&self) -> f64 {
+        f64::from(self.lng) / MAS_PER_DEGREE
+    }
 
-        // Skip whitespace and optional comma
-        let rest = rest.trim_start();
-        let rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    /// Returns the latitude in milli-arc-seconds.
+    pub fn lat_mas(&self) -> i32 {
+        self.lat
+    }
+
+    /// Returns the longitude in milli-arc-seconds.
+    pub fn lng_mas(&self) -> i32 {
+        self.lng
+    }
 
-        // Parse longitude coordinate and direction
-        let (mut lng, rest) = parse_coord_component(rest, false).map_err(|_| err())?;
-        let (lng_is_negative, _rest) = parse_direction(rest, false).map_err(|_| err())?;
-        if lng_is_negative {
-            lng = -lng;
+    /// Parse a coordinate, auto-detecting the notation.
+    ///
+    /// See [`CoordFormat`] for the supported notations. Input that could be
+    /// read in several notations with differing results is rejected.
+    pub fn parse(data: &str) -> Result<Self, String> {
+        Self::parse_as(data, &CoordFormat::ALL)
+    }
+
+    /// Parse a coordinate from a record of an OpenAir file.
+    ///
+    /// Files almost always use the OpenAir notation, so it is tried first,
+    /// before falling back to auto-detection (see [`Self::parse`]).
+    pub(crate) fn parse_record(data: &str) -> Result<Self, String> {
+        CoordFormat::OpenAir
+            .parse(data.trim())
+            .or_else(|_| Self::parse(data))
+    }
+
+    /// Parse a coordinate in one of the given notations.
+    pub fn parse_as(data: &str, formats: &[CoordFormat]) -> Result<Self, String> {
+        let input = data.trim();
+
+        let mut found: Option<(CoordFormat, Coord)> = None;
+        for format in formats {
+            let Ok(coord) = format.parse(input) else {
+                continue;
+            };
+            match found {
+                None => found = Some((*format, coord)),
+                Some((_, ref other)) if *other == coord => {}
+                Some((other_format, _)) => {
+                    return Err(format!(
+                        "Ambiguous coord: \"{data}\" (could be {} or {} notation)",
+                        other_format.name(),
+                        format.name(),
+                    ));
+                }
+            }
         }
 
-        Ok(Self { lat, lng })
+        match (found, formats) {
+            (Some((_, coord)), _) => Ok(coord),
+            (None, [format]) => Err(format!(
+                "Invalid coord: \"{data}\" (expected {} notation)",
+                format.name()
+            )),
+            _ => Err(format!("Invalid coord: \"{data}\"")),
+        }
     }
 
     /// Writes coordinate in OpenAir DMS format.
     ///
-    /// Format: `DD:MM:SS N/S DDD:MM:SS E/W`
-    pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
-        let total = (self.lat.abs() * 3600.0).round();
-        let lat_deg = (total / 3600.0).trunc() as u16;
-        let remaining = total - f64::from(lat_deg) * 3600.0;
-        let lat_min = (remaining / 60.0).trunc() as u16;
-        let lat_sec = (remaining - f64::from(lat_min) * 60.0) as u16;
-        let lat_dir = if self.lat >= 0.0 { 'N' } else { 'S' };
-
-        let total = (self.lng.abs() * 3600.0).round();
-        let lng_deg = (total / 3600.0).trunc() as u16;
-        let remaining = total - f64::from(lng_deg) * 3600.0;
-        let lng_min = (remaining / 60.0).trunc() as u16;
-        let lng_sec = (remaining - f64::from(lng_min) * 60.0) as u16;
-        let lng_dir = if self.lng >= 0.0 { 'E' } else { 'W' };
-
-        write!(
-            writer,
-            "{lat_deg:02}:{lat_min:02}:{lat_sec:02} {lat_dir} {lng_deg:03}:{lng_min:02}:{lng_sec:02} {lng_dir}",
-        )
+    /// Format: `DD:MM:SS N/S DDD:MM:SS E/W`. Fractional seconds are only
+    /// written if needed, so that no precision is lost.
+    pub fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        self.write_with(writer, &WriterOptions::default())
+    }
+
+    /// Writes coordinate in the notation configured in the options.
+    pub fn write_with<W: Write>(
+        &self,
+        mut writer: W,
+        options: &WriterOptions,
+    ) -> std::io::Result<()> {
+        let lat_dir = if self.lat >= 0 { 'N' } else { 'S' };
+        let lng_dir = if self.lng >= 0 { 'E' } else { 'W' };
+
+        let style = options.coord_style;
+        write_angle(&mut writer, self.lat.unsigned_abs(), 2, style)?;
+        write!(writer, " {lat_dir} ")?;
+        write_angle(&mut writer, self.lng.unsigned_abs(), 3, style)?;
+        write!(writer, " {lng_dir}")
+    }
+}
+
+impl Coord {
+    /// Returns the geodesic distance to another coordinate in metres.
+    ///
+    /// Distances and bearings are computed on the WGS84 ellipsoid using
+    /// Vincenty's formulae, which are accurate to well below a millimetre.
+    /// For nearly antipodal points, where the formulae do not converge, a
+    /// spherical model is used instead, with an error of up to 0.5%.
+    pub fn distance_to(&self, other: &Coord) -> f64 {
+        geodesy::inverse(self, other).distance
+    }
+
+    /// Returns the initial bearing of the geodesic to another coordinate in
+    /// degrees (0..360, clockwise from true north). Returns 0 for equal
+    /// coordinates.
+    pub fn bearing_to(&self, other: &Coord) -> f64 {
+        geodesy::inverse(self, other).initial_bearing
+    }
+
+    /// Returns the coordinate reached when travelling `distance` metres along
+    /// the geodesic with the given initial bearing (degrees).
+    ///
+    /// The result is rounded to milli-arc-seconds (about 3 cm).
+    pub fn destination(&self, bearing: f64, distance: f64) -> Coord {
+        geodesy::direct(self, bearing, distance)
+    }
+
+    /// Like [`Self::distance_to`], but on a sphere with the mean earth
+    /// radius. Faster, with an error of up to 0.5%.
+    pub fn distance_to_spherical(&self, other: &Coord) -> f64 {
+        geodesy::spherical_inverse(self.lat(), self.lng(), other.lat(), other.lng()).distance
+    }
+
+    /// Like [`Self::bearing_to`], but on a sphere with the mean earth radius.
+    pub fn bearing_to_spherical(&self, other: &Coord) -> f64 {
+        geodesy::spherical_inverse(self.lat(), self.lng(), other.lat(), other.lng()).initial_bearing
+    }
+
+    /// Like [`Self::destination`], but on a sphere with the mean earth
+    /// radius.
+    pub fn destination_spherical(&self, bearing: f64, distance: f64) -> Coord {
+        let (lat, lng) = geodesy::spherical_direct(self.lat(), self.lng(), bearing, distance);
+        Coord::from_degrees(lat, lng)
+    }
+}
+
+/// Write an absolute angle in milli-arc-seconds in the given style.
+fn write_angle<W: Write>(
+    mut writer: W,
+    mas: u32,
+    deg_width: usize,
+    style: CoordStyle,
+) -> std::io::Result<()> {
+    match style {
+        CoordStyle::Dms => write_dms(writer, mas, deg_width),
+        CoordStyle::DmsDecimal(decimals) => {
+            let decimals = u32::from(decimals.min(3));
+            let unit = 10u32.pow(3 - decimals);
+            let scale = 10u32.pow(decimals);
+            // Round to the requested precision first, so that carries
+            // propagate into minutes and degrees
+            let total = (mas + unit / 2) / unit;
+            let secs = total / scale;
+            let (deg, min, sec) = (secs / 3600, secs / 60 % 60, secs % 60);
+            write!(writer, "{deg:0deg_width$}:{min:02}:{sec:02}")?;
+            if decimals > 0 {
+                let width = decimals as usize;
+                write!(writer, ".{:0width$}", total % scale)?;
+            }
+            Ok(())
+        }
+        CoordStyle::Ddm(decimals) => {
+            let decimals = u32::from(decimals.min(5));
+            let scale = 10u64.pow(decimals);
+            let total = (u64::from(mas) * scale + 30_000) / 60_000;
+            let mins = total / scale;
+            let (deg, min) = (mins / 60, mins % 60);
+            write!(writer, "{deg:0deg_width$}:{min:02}")?;
+            if decimals > 0 {
+                let width = decimals as usize;
+                write!(writer, ".{:0width$}", total % scale)?;
+            }
+            Ok(())
+        }
     }
 }
 
+/// Write an absolute angle in milli-arc-seconds as `D:MM:SS[.sss]`.
+fn write_dms<W: Write>(mut writer: W, mas: u32, deg_width: usize) -> std::io::Result<()> {
+    let deg = mas / 3_600_000;
+    let min = mas / 60_000 % 60;
+    let sec = mas / 1000 % 60;
+    let frac = mas % 1000;
+    write!(writer, "{deg:0deg_width$}:{min:02}:{sec:02}")?;
+    if frac != 0 {
+        let frac = format!("{frac:03}");
+        write!(writer, ".{}", frac.trim_end_matches('0'))?;
+    }
+    Ok(())
+}
+
 fn parse_coord_component(input: &str, is_lat: bool) -> Result<(f64, &str), ()> {
     // Parse degrees
     let pos = input.find(|c: char| !c.is_ascii_digit()).ok_or(())?;
@@ -160,6 +423,232 @@ fn parse_direction(input: &str, is_lat: bool) -> Result<(bool, &str), ()> {
     Ok((is_negative, &input[ch.len_utf8()..]))
 }
 
+/// Parse the OpenAir notation (DMS or DDM with colon separators).
+fn parse_openair(input: &str) -> Result<Coord, ()> {
+    // Parse latitude coordinate and direction
+    let (mut lat, rest) = parse_coord_component(input, true)?;
+    let (lat_is_negative, rest) = parse_direction(rest, true)?;
+    if lat_is_negative {
+        lat = -lat;
+    }
+
+    // Skip whitespace and optional comma
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+
+    // Parse longitude coordinate and direction
+    let (mut lng, rest) = parse_coord_component(rest, false)?;
+    let (lng_is_negative, _rest) = parse_direction(rest, false)?;
+    if lng_is_negative {
+        lng = -lng;
+    }
+
+    Ok(Coord::from_degrees(lat, lng))
+}
+
+/// A minimal cursor over the input, used by the non-OpenAir notations.
+struct Scanner<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Scanner<'a> {
+    fn skip_whitespace(&mut self) -> bool {
+        let trimmed = self.rest.trim_start();
+        let skipped = trimmed.len() != self.rest.len();
+        self.rest = trimmed;
+        skipped
+    }
+
+    /// Consume one of the given characters.
+    fn eat(&mut self, chars: &[char]) -> bool {
+        match self.rest.chars().next() {
+            Some(c) if chars.contains(&c) => {
+                self.rest = &self.rest[c.len_utf8()..];
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Consume an unsigned number with optional fractional part.
+    fn number(&mut self) -> Option<&'a str> {
+        let int_len = self
+            .rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len());
+        if int_len == 0 {
+            return None;
+        }
+        let mut len = int_len;
+        if let Some(frac) = self.rest[int_len..].strip_prefix('.') {
+            let frac_len = frac
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(frac.len());
+            if frac_len > 0 {
+                len += 1 + frac_len;
+            }
+        }
+        let (number, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(number)
+    }
+
+    /// Consume a hemisphere letter, returns true for S/W.
+    fn hemisphere(&mut self, is_lat: bool) -> Option<bool> {
+        let (positive, negative) = if is_lat { ('N', 'S') } else { ('E', 'W') };
+        let c = self.rest.chars().next()?.to_ascii_uppercase();
+        let is_negative = match c {
+            c if c == positive => false,
+            c if c == negative => true,
+            _ => return None,
+        };
+        self.rest = &self.rest[1..];
+        Some(is_negative)
+    }
+}
+
+/// Parse a latitude/longitude pair, separated by whitespace and/or a comma.
+fn parse_pair(input: &str, component: fn(&mut Scanner, bool) -> Option<f64>) -> Result<Coord, ()> {
+    let mut scanner = Scanner { rest: input };
+    let lat = component(&mut scanner, true).ok_or(())?;
+    // A separator is only optional after a trailing hemisphere letter
+    let consumed = &input[..input.len() - scanner.rest.len()];
+    let had_whitespace = scanner.skip_whitespace();
+    if !scanner.eat(&[',']) && !had_whitespace && !consumed.ends_with(char::is_alphabetic) {
+        return Err(());
+    }
+    scanner.skip_whitespace();
+    let lng = component(&mut scanner, false).ok_or(())?;
+    if !scanner.rest.is_empty() {
+        return Err(());
+    }
+    Ok(Coord::from_degrees(lat, lng))
+}
+
+/// Combine degree, minute and second fields into decimal degrees.
+///
+/// Only the last field may have a fractional part.
+fn combine_fields(fields: &[&str], is_lat: bool) -> Option<f64> {
+    let (_, init) = fields.split_last()?;
+    if init.iter().any(|field| field.contains('.')) {
+        return None;
+    }
+
+    let mut total = 0.0;
+    for (i, field) in fields.iter().enumerate() {
+        let value = field.parse::<f64>().ok()?;
+        if i > 0 && value >= 60.0 {
+            return None;
+        }
+        total += value / 60f64.powi(i as i32);
+    }
+
+    let max = if is_lat { 90.0 } else { 180.0 };
+    (total <= max).then_some(total)
+}
+
+fn apply_sign(value: f64, is_negative: bool) -> f64 {
+    if is_negative { -value } else { value }
+}
+
+/// `DDMM[SS][.s]H` / `DDDMM[SS][.s]H`
+fn compact_component(scanner: &mut Scanner, is_lat: bool) -> Option<f64> {
+    let number = scanner.number()?;
+    let int_len = number.find('.').unwrap_or(number.len());
+    let deg_digits = if is_lat { 2 } else { 3 };
+    let fields = match int_len.checked_sub(deg_digits)? {
+        2 => vec![&number[..deg_digits], &number[deg_digits..]],
+        4 => vec![
+            &number[..deg_digits],
+            &number[deg_digits..deg_digits + 2],
+            &number[deg_digits + 2..],
+        ],
+        _ => return None,
+    };
+    let value = combine_fields(&fields, is_lat)?;
+    scanner.skip_whitespace();
+    let is_negative = scanner.hemisphere(is_lat)?;
+    Some(apply_sign(value, is_negative))
+}
+
+/// `DD.ddddH` / `DDD.ddddH`
+fn decimal_hemisphere_component(scanner: &mut Scanner, is_lat: bool) -> Option<f64> {
+    let value = combine_fields(&[scanner.number()?], is_lat)?;
+    scanner.skip_whitespace();
+    let is_negative = scanner.hemisphere(is_lat)?;
+    Some(apply_sign(value, is_negative))
+}
+
+/// `[-]DD.dddd` / `[-]DDD.dddd`
+fn decimal_signed_component(scanner: &mut Scanner, is_lat: bool) -> Option<f64> {
+    let is_negative = scanner.eat(&['-']);
+    if !is_negative {
+        scanner.eat(&['+']);
+    }
+    let value = combine_fields(&[scanner.number()?], is_lat)?;
+    Some(apply_sign(value, is_negative))
+}
+
+/// `HDD[ MM[ SS]]` with whitespace or colons between the fields
+fn hemisphere_first_component(scanner: &mut Scanner, is_lat: bool) -> Option<f64> {
+    let is_negative = scanner.hemisphere(is_lat)?;
+    scanner.skip_whitespace();
+    let mut fields = vec![scanner.number()?];
+    while fields.len() < 3 {
+        let saved = scanner.rest;
+        if !scanner.eat(&[':']) && !scanner.skip_whitespace() {
+            break;
+        }
+        match scanner.number() {
+            Some(field) => fields.push(field),
+            None => {
+                scanner.rest = saved;
+                break;
+            }
+        }
+    }
+    let value = combine_fields(&fields, is_lat)?;
+    Some(apply_sign(value, is_negative))
+}
+
+/// `[H]DD°[MM'[SS"]][H]` with exactly one hemisphere letter
+fn symbol_component(scanner: &mut Scanner, is_lat: bool) -> Option<f64> {
+    const SYMBOLS: [&[char]; 3] = [&['°', 'º'], &['\'', '′', '’'], &['"', '″', '”']];
+
+    let prefix = scanner.hemisphere(is_lat);
+    scanner.skip_whitespace();
+
+    let mut fields = Vec::new();
+    for symbols in SYMBOLS {
+        let saved = scanner.rest;
+        scanner.skip_whitespace();
+        let Some(field) = scanner.number() else {
+            scanner.rest = saved;
+            break;
+        };
+        scanner.skip_whitespace();
+        if !scanner.eat(symbols) {
+            // Allow two single quotes as seconds symbol
+            if symbols.contains(&'"') && scanner.rest.starts_with("''") {
+                scanner.rest = &scanner.rest[2..];
+            } else {
+                return None;
+            }
+        }
+        fields.push(field);
+    }
+    let value = combine_fields(&fields, is_lat)?;
+
+    let is_negative = match prefix {
+        Some(is_negative) => is_negative,
+        None => {
+            scanner.skip_whitespace();
+            scanner.hemisphere(is_lat)?
+        }
+    };
+    Some(apply_sign(value, is_negative))
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_compact_debug_snapshot;
@@ -184,7 +673,7 @@ mod tests {
         assert_compact_debug_snapshot!(Coord::parse("1:0:0.123 N 2:0:1.2 E"), @"Ok(Coord { lat: 1.0000341666666666, lng: 2.0003333333333333 })");
 
         // Comma in between
-        assert_compact_debug_snapshot!(Coord::parse("45:42:21 N, 000:38:41 W"), @"Ok(Coord { lat: 45.70583333333334, lng: -0.6447222222222222 })");
+        assert_compact_debug_snapshot!(Coord::parse("45:42:21 N, 000:38:41 W"), @"Ok(Coord { lat: 45.70583333333333, lng: -0.6447222222222222 })");
 
         // Lowercase letters
         assert_compact_debug_snapshot!(Coord::parse("49:33:8 n 5:47:37 e"), @"Ok(Coord { lat: 49.55222222222222, lng: 5.793611111111111 })");
@@ -231,8 +720,89 @@ mod tests {
         assert_compact_debug_snapshot!(Coord::parse("46x51x44 S 009x19x42 W"), @r#"Err("Invalid coord: \"46x51x44 S 009x19x42 W\"")"#);
     }
 
+    #[test]
+    fn parse_compact() {
+        assert_compact_debug_snapshot!(Coord::parse("473015N 0083020E"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.505555555555556 })");
+        assert_compact_debug_snapshot!(Coord::parse("473015.5S0083020.5W"), @"Ok(Coord { lat: -47.504305555555554, lng: -8.505694444444444 })");
+        assert_compact_debug_snapshot!(Coord::parse("4730.25N, 00830.33E"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.5055 })");
+        assert_compact_debug_snapshot!(Coord::parse("473015 N 0083020 E"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.505555555555556 })");
+    }
+
+    #[test]
+    fn parse_decimal() {
+        assert_compact_debug_snapshot!(Coord::parse("47.5042N 8.5056E"), @"Ok(Coord { lat: 47.5042, lng: 8.5056 })");
+        assert_compact_debug_snapshot!(Coord::parse("47.5042 S, 8.5056 W"), @"Ok(Coord { lat: -47.5042, lng: -8.5056 })");
+        assert_compact_debug_snapshot!(Coord::parse("47.5042, 8.5056"), @"Ok(Coord { lat: 47.5042, lng: 8.5056 })");
+        assert_compact_debug_snapshot!(Coord::parse("-47.5042 -8.5056"), @"Ok(Coord { lat: -47.5042, lng: -8.5056 })");
+        assert_compact_debug_snapshot!(Coord::parse("47, -120"), @"Ok(Coord { lat: 47.0, lng: -120.0 })");
+    }
+
+    #[test]
+    fn parse_hemisphere_first() {
+        assert_compact_debug_snapshot!(Coord::parse("N47 30.25 E008 30.33"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.5055 })");
+        assert_compact_debug_snapshot!(Coord::parse("N47:30:15 E8:30:20"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.505555555555556 })");
+        assert_compact_debug_snapshot!(Coord::parse("S47 30 15.5, W8 30 20"), @"Ok(Coord { lat: -47.504305555555554, lng: -8.505555555555556 })");
+        assert_compact_debug_snapshot!(Coord::parse("N47.5042 E8.5056"), @"Ok(Coord { lat: 47.5042, lng: 8.5056 })");
+    }
+
+    #[test]
+    fn parse_symbols() {
+        assert_compact_debug_snapshot!(Coord::parse("47°30'15\"N 8°30'20\"E"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.505555555555556 })");
+        assert_compact_debug_snapshot!(Coord::parse("47° 30′ 15.5″ S, 8° 30′ 20″ W"), @"Ok(Coord { lat: -47.504305555555554, lng: -8.505555555555556 })");
+        assert_compact_debug_snapshot!(Coord::parse("N47°30.25' E8°30.33'"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.5055 })");
+        assert_compact_debug_snapshot!(Coord::parse("47.5042°N 8.5056°E"), @"Ok(Coord { lat: 47.5042, lng: 8.5056 })");
+        assert_compact_debug_snapshot!(Coord::parse("47°30'15''N 8°30'20''E"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.505555555555556 })");
+    }
+
+    #[test]
+    fn parse_as() {
+        // Restricting the notations
+        assert_compact_debug_snapshot!(Coord::parse_as("473015N 0083020E", &[CoordFormat::Compact]), @"Ok(Coord { lat: 47.50416666666667, lng: 8.505555555555556 })");
+        assert_compact_debug_snapshot!(Coord::parse_as("473015N 0083020E", &[CoordFormat::OpenAir]), @r#"Err("Invalid coord: \"473015N 0083020E\" (expected OpenAir notation)")"#);
+        assert_compact_debug_snapshot!(Coord::parse_as("47.5, 8.5", &[CoordFormat::OpenAir, CoordFormat::Compact]), @r#"Err("Invalid coord: \"47.5, 8.5\"")"#);
+    }
+
+    #[test]
+    fn parse_record() {
+        // OpenAir notation first, other notations as a fallback
+        assert_compact_debug_snapshot!(Coord::parse_record(" 46:51:44 N 009:19:42 E"), @"Ok(Coord { lat: 46.86222222222222, lng: 9.328333333333333 })");
+        assert_compact_debug_snapshot!(Coord::parse_record("473015N 0083020E"), @"Ok(Coord { lat: 47.50416666666667, lng: 8.505555555555556 })");
+        assert_compact_debug_snapshot!(Coord::parse_record("foo"), @r#"Err("Invalid coord: \"foo\"")"#);
+    }
+
+    #[test]
+    fn parse_invalid_notations() {
+        // Minutes / seconds out of range
+        assert_compact_debug_snapshot!(Coord::parse("476015N 0083020E"), @r#"Err("Invalid coord: \"476015N 0083020E\"")"#);
+        assert_compact_debug_snapshot!(Coord::parse("N47 60 E8 30"), @r#"Err("Invalid coord: \"N47 60 E8 30\"")"#);
+        // Degrees out of range
+        assert_compact_debug_snapshot!(Coord::parse("91.5, 8.5"), @r#"Err("Invalid coord: \"91.5, 8.5\"")"#);
+        // Fractional part on a field that isn't the last one
+        assert_compact_debug_snapshot!(Coord::parse("N47.5 30 E8 30"), @r#"Err("Invalid coord: \"N47.5 30 E8 30\"")"#);
+        // Missing / duplicate hemisphere
+        assert_compact_debug_snapshot!(Coord::parse("47°30' 8°30'E"), @r#"Err("Invalid coord: \"47°30' 8°30'E\"")"#);
+        assert_compact_debug_snapshot!(Coord::parse("N47°30'N E8°30'"), @r#"Err("Invalid coord: \"N47°30'N E8°30'\"")"#);
+        // Wrong digit count in compact notation
+        assert_compact_debug_snapshot!(Coord::parse("47301N 0083020E"), @r#"Err("Invalid coord: \"47301N 0083020E\"")"#);
+        // Missing separator
+        assert_compact_debug_snapshot!(Coord::parse("-47.5-8.5"), @r#"Err("Invalid coord: \"-47.5-8.5\"")"#);
+        // Trailing garbage
+        assert_compact_debug_snapshot!(Coord::parse("47.5, 8.5, 9.5"), @r#"Err("Invalid coord: \"47.5, 8.5, 9.5\"")"#);
+    }
+
+    #[test]
+    fn try_from_degrees() {
+        assert_eq!(
+            Coord::try_from_degrees(-90.0, 180.0),
+            Ok(Coord::from_degrees(-90.0, 180.0))
+        );
+        assert_compact_debug_snapshot!(Coord::try_from_degrees(1000.0, 8.0), @r#"Err("Latitude out of range: 1000")"#);
+        assert_compact_debug_snapshot!(Coord::try_from_degrees(47.0, -180.5), @r#"Err("Longitude out of range: -180.5")"#);
+        assert!(Coord::try_from_degrees(f64::NAN, 8.0).is_err());
+    }
+
     fn lat_lng(lat: f64, lng: f64) -> Coord {
-        Coord { lat, lng }
+        Coord::from_degrees(lat, lng)
     }
 
     fn write_coord(coord: &Coord) -> String {
@@ -267,43 +837,146 @@ mod tests {
         // 3-digit longitude degrees
         assert_compact_debug_snapshot!(
             write_coord(&lat_lng(0.0, 123.456789)),
-            @r#""00:00:00 N 123:27:24 E""#
+            @r#""00:00:00 N 123:27:24.44 E""#
         );
 
-        // Rounding down (< 0.5 seconds)
+        // Fractional seconds are kept
         assert_compact_debug_snapshot!(
             write_coord(&lat_lng(
                 1.0 + 0.0 / 60.0 + 0.4 / 3600.0,
-                2.0 + 0.0 / 60.0 + 0.4 / 3600.0,
-            )),
-            @r#""01:00:00 N 002:00:00 E""#
-        );
-
-        // Rounding up (≥ 0.5 seconds)
-        assert_compact_debug_snapshot!(
-            write_coord(&lat_lng(
-                1.0 + 0.0 / 60.0 + 0.5 / 3600.0,
                 2.0 + 0.0 / 60.0 + 0.5 / 3600.0,
             )),
-            @r#""01:00:01 N 002:00:01 E""#
+            @r#""01:00:00.4 N 002:00:00.5 E""#
         );
-
-        // Rounding causes seconds rollover to minutes
         assert_compact_debug_snapshot!(
             write_coord(&lat_lng(
-                1.0 + 30.0 / 60.0 + 59.5 / 3600.0,
-                2.0 + 45.0 / 60.0 + 59.5 / 3600.0,
+                1.0 + 30.0 / 60.0 + 59.125 / 3600.0,
+                2.0 + 45.0 / 60.0 + 59.05 / 3600.0,
             )),
-            @r#""01:31:00 N 002:46:00 E""#
+            @r#""01:30:59.125 N 002:45:59.05 E""#
         );
 
-        // Rounding causes minutes rollover to degrees
+        // Rounding to milli-arc-seconds causes rollover to degrees
         assert_compact_debug_snapshot!(
             write_coord(&lat_lng(
-                1.0 + 59.0 / 60.0 + 59.5 / 3600.0,
-                2.0 + 59.0 / 60.0 + 59.5 / 3600.0,
+                1.0 + 59.0 / 60.0 + 59.9996 / 3600.0,
+                2.0 + 59.0 / 60.0 + 59.9996 / 3600.0,
             )),
             @r#""02:00:00 N 003:00:00 E""#
         );
     }
+
+    #[test]
+    fn write_styles() {
+        let write = |coord: &Coord, coord_style| {
+            let options = WriterOptions {
+                coord_style,
+                ..Default::default()
+            };
+            let mut buf = Vec::new();
+            coord.write_with(&mut buf, &options).unwrap();
+            String::from_utf8(buf).unwrap()
+        };
+        let coord = Coord::parse("47:30:15.5 N 008:30:59.75 W").unwrap();
+
+        assert_eq!(
+            write(&coord, CoordStyle::Dms),
+            "47:30:15.5 N 008:30:59.75 W"
+        );
+        assert_eq!(
+            write(&coord, CoordStyle::DmsDecimal(0)),
+            "47:30:16 N 008:31:00 W"
+        );
+        assert_eq!(
+            write(&coord, CoordStyle::DmsDecimal(1)),
+            "47:30:15.5 N 008:30:59.8 W"
+        );
+        assert_eq!(
+            write(&coord, CoordStyle::DmsDecimal(9)),
+            "47:30:15.500 N 008:30:59.750 W"
+        );
+        assert_eq!(write(&coord, CoordStyle::Ddm(0)), "47:30 N 008:31 W");
+        assert_eq!(
+            write(&coord, CoordStyle::Ddm(3)),
+            "47:30.258 N 008:30.996 W"
+        );
+        assert_eq!(
+            write(&coord, CoordStyle::Ddm(9)),
+            "47:30.25833 N 008:30.99583 W"
+        );
+
+        // Rounding carries into degrees
+        let coord = Coord::parse("01:59:59.6 N 179:59:59.6 E").unwrap();
+        assert_eq!(
+            write(&coord, CoordStyle::DmsDecimal(0)),
+            "02:00:00 N 180:00:00 E"
+        );
+        assert_eq!(write(&coord, CoordStyle::Ddm(1)), "02:00.0 N 180:00.0 E");
+    }
+
+    #[test]
+    fn write_roundtrip() {
+        for input in [
+            "47:30:15.5 N 008:30:20.25 W",
+            "00:00:00.001 S 179:59:59.999 E",
+            "90:00:00 N 180:00:00 W",
+        ] {
+            let coord = Coord::parse(input).unwrap();
+            assert_eq!(write_coord(&coord), input);
+            assert_eq!(Coord::parse(&write_coord(&coord)).unwrap(), coord);
+        }
+
+        // DDM and decimal notations are written as DMS without loss
+        let coord = Coord::parse("46:51.44 N 009:19.42 E").unwrap();
+        assert_eq!(write_coord(&coord), "46:51:26.4 N 009:19:25.2 E");
+        let coord = Coord::parse("47.504213, -8.5").unwrap();
+        assert_eq!(write_coord(&coord), "47:30:15.167 N 008:30:00 W");
+    }
+
+    #[test]
+    fn exact_representation() {
+        let coord = Coord::parse("47:30:15.5 N 008:30:20 E").unwrap();
+        assert_eq!(coord.lat_mas(), 171_015_500);
+        assert_eq!(coord.lng_mas(), 30_620_000);
+        assert_eq!(Coord::from_mas(171_015_500, 30_620_000), coord);
+
+        // Same point in different notations compares equal
+        assert_eq!(Coord::parse("47°30'15.5\"N 8°30'20\"E").unwrap(), coord);
+        assert_eq!(Coord::parse("473015.5N 0083020E").unwrap(), coord);
+
+        // Ordering is by latitude, then longitude
+        let north = Coord::from_degrees(48.0, 0.0);
+        let east = Coord::from_degrees(47.0, 9.0);
+        assert!(north > east);
+    }
+
+    #[test]
+    fn geodesic() {
+        // Flinders Peak to Buninyong (Vincenty's reference example)
+        let flinders = Coord::parse("37:57:03.720 S 144:25:29.524 E").unwrap();
+        let buninyong = Coord::parse("37:39:10.156 S 143:55:35.384 E").unwrap();
+        let bearing = 306.0 + 52.0 / 60.0 + 5.37 / 3600.0;
+
+        // Coordinates are rounded to milli-arc-seconds
+        assert!((flinders.distance_to(&buninyong) - 54_972.271).abs() < 0.05);
+        assert!((flinders.bearing_to(&buninyong) - bearing).abs() < 1e-5);
+        let destination = flinders.destination(bearing, 54_972.271);
+        assert!(destination.distance_to(&buninyong) < 0.05);
+
+        assert_eq!(flinders.distance_to(&flinders), 0.0);
+        assert_eq!(flinders.bearing_to(&flinders), 0.0);
+    }
+
+    #[test]
+    fn spherical() {
+        let a = Coord::from_degrees(47.0, 8.0);
+        let b = Coord::from_degrees(46.5, 9.2);
+        let distance = a.distance_to(&b);
+        assert!((a.distance_to_spherical(&b) - distance).abs() / distance < 0.005);
+        assert!((a.bearing_to_spherical(&b) - a.bearing_to(&b)).abs() < 0.5);
+
+        let destination = a.destination_spherical(90.0, 10_000.0);
+        assert!((a.distance_to_spherical(&destination) - 10_000.0).abs() < 0.05);
+        assert!(destination.distance_to(&a.destination(90.0, 10_000.0)) < 50.0);
+    }
 }
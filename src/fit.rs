@@ -0,0 +1,416 @@
+use std::f64::consts::{PI, TAU};
+
+use crate::{ArcSegment, Coord, Direction, Geometry, PolygonSegment, geodesy};
+
+/// Minimum number of points replaced by an arc.
+const MIN_ARC_POINTS: usize = 5;
+
+/// Minimum number of points of a polygon replaced by a circle.
+const MIN_CIRCLE_POINTS: usize = 8;
+
+/// Maximum radius of fitted arcs and circles in metres (100 NM).
+const MAX_RADIUS: f64 = 100.0 * geodesy::METRES_PER_NM;
+
+impl Geometry {
+    /// Replace runs of polygon points lying on a circular arc by arc
+    /// segments, and polygons whose points all lie on a circle by a circle.
+    /// Returns the number of removed segments.
+    ///
+    /// Every replaced point lies within `tolerance` metres of the fitted arc,
+    /// and the arc stays within `tolerance` metres of the edges between
+    /// the points. At least five consecutive points turning in the same
+    /// direction are needed for an arc, and straight runs are never replaced.
+    /// The replaced arcs start and end at the bearings of the first and last
+    /// point of the run, existing arcs are kept unchanged. The radius and
+    /// angles of fitted arcs are rounded to 0.001 NM and 0.01° if the rounded
+    /// arc still meets the tolerance.
+    ///
+    /// ```
+    /// use openair::{Coord, Geometry, PolygonSegment};
+    ///
+    /// let center = Coord::from_degrees(47.0, 8.0);
+    /// let mut geom = Geometry::Polygon {
+    ///     segments: (0..360)
+    ///         .map(|i| PolygonSegment::Point(center.destination(f64::from(i), 9260.0)))
+    ///         .collect(),
+    /// };
+    /// assert_eq!(geom.fit_arcs(1.0), 359);
+    /// let Geometry::Circle { centerpoint, radius } = geom else { panic!() };
+    /// assert!(centerpoint.distance_to(&center) < 1.0);
+    /// assert!((radius - 5.0).abs() < 0.001);
+    /// ```
+    pub fn fit_arcs(&mut self, tolerance: f64) -> usize {
+        let Self::Polygon { segments } = self else {
+            return 0;
+        };
+        let original_len = segments.len();
+
+        if let Some((centerpoint, radius)) = fit_circle(segments, tolerance) {
+            *self = Self::Circle {
+                centerpoint,
+                radius: (radius / geodesy::METRES_PER_NM) as f32,
+            };
+            return original_len - 1;
+        }
+
+        let original = std::mem::take(segments);
+        let mut run: Vec<Coord> = Vec::new();
+        for segment in original {
+            match segment {
+                PolygonSegment::Point(point) => run.push(point),
+                segment => {
+                    fit_run(&run, tolerance, segments);
+                    run.clear();
+                    segments.push(segment);
+                }
+            }
+        }
+        fit_run(&run, tolerance, segments);
+        original_len - segments.len()
+    }
+}
+
+/// A circle in a local projection, in metres east and north.
+#[derive(Debug)]
+struct Circle {
+    x: f64,
+    y: f64,
+    radius: f64,
+    /// Signed sum of the angles between consecutive points, positive if the
+    /// points run counterclockwise
+    sweep: f64,
+}
+
+/// Center and radius of the circle through all points of a polygon that
+/// consists of points only, if any.
+fn fit_circle(segments: &[PolygonSegment], tolerance: f64) -> Option<(Coord, f64)> {
+    let mut points = segments
+        .iter()
+        .map(|segment| match segment {
+            PolygonSegment::Point(point) => Some(*point),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < MIN_CIRCLE_POINTS {
+        return None;
+    }
+    let (center, _) = fit_geodesic(&points, &points[0], tolerance, true)?;
+    let (center, circle) = fit_geodesic(&points, &center, tolerance, true)?;
+    ((circle.sweep.abs() - TAU).abs() < 1e-6).then_some((center, circle.radius))
+}
+
+/// Replace the points of a run by points and fitted arcs, appending them to
+/// `segments`.
+fn fit_run(run: &[Coord], tolerance: f64, segments: &mut Vec<PolygonSegment>) {
+    let mut i = 0;
+    while i < run.len() {
+        match longest_arc(&run[i..], tolerance) {
+            Some((arc, len)) => {
+                segments.push(PolygonSegment::ArcSegment(arc));
+                i += len;
+            }
+            None => {
+                segments.push(PolygonSegment::Point(run[i]));
+                i += 1;
+            }
+        }
+    }
+}
+
+/// The longest arc through the first points of the run, and the number of
+/// points it replaces.
+fn longest_arc(run: &[Coord], tolerance: f64) -> Option<(ArcSegment, usize)> {
+    if run.len() < MIN_ARC_POINTS {
+        return None;
+    }
+
+    // Grow the arc as long as the points fit, using a projection around the
+    // first point
+    let origin = &run[0];
+    let planar: Vec<_> = run.iter().map(|point| project(origin, point)).collect();
+    let mut len = 0;
+    for end in MIN_ARC_POINTS..=run.len() {
+        if fit(&planar[..end], tolerance, false).is_none() {
+            break;
+        }
+        len = end;
+    }
+
+    // Verify the arc in a projection around its center, which is exact for
+    // the distances to the center. Distortions of the first projection may
+    // require a shorter arc.
+    while len >= MIN_ARC_POINTS {
+        let points = &run[..len];
+        if let Some((center, _)) = fit_geodesic(points, origin, tolerance, false)
+            && let Some((center, circle)) = fit_geodesic(points, &center, tolerance, false)
+        {
+            let bearing = |point: &Coord| geodesy::inverse(&center, point).initial_bearing as f32;
+            let arc = ArcSegment {
+                centerpoint: center,
+                radius: (circle.radius / geodesy::METRES_PER_NM) as f32,
+                angle_start: bearing(&points[0]),
+                angle_end: bearing(&points[len - 1]),
+                direction: if circle.sweep > 0.0 {
+                    Direction::Ccw
+                } else {
+                    Direction::Cw
+                },
+            };
+            // Prefer rounded values, unless they move the arc too far
+            let rounded = arc.rounded();
+            if fits(&rounded, points, tolerance) {
+                return Some((rounded, len));
+            }
+            return Some((arc, len));
+        }
+        len -= 1;
+    }
+    None
+}
+
+/// Returns true if all points lie within the tolerance of the arc, and the
+/// arc starts and ends within the tolerance of the first and last point.
+fn fits(arc: &ArcSegment, points: &[Coord], tolerance: f64) -> bool {
+    let radius = f64::from(arc.radius) * geodesy::METRES_PER_NM;
+    let edge = arc.to_arc();
+    let on_arc = |point: &Coord| {
+        (geodesy::inverse(&arc.centerpoint, point).distance - radius).abs() <= tolerance
+    };
+    points.iter().all(on_arc)
+        && geodesy::inverse(&edge.start, &points[0]).distance <= tolerance
+        && geodesy::inverse(&edge.end, &points[points.len() - 1]).distance <= tolerance
+}
+
+/// Fit a circle to the points in an azimuthal equidistant projection around
+/// `origin`, returning its center and the circle in that projection.
+fn fit_geodesic(
+    points: &[Coord],
+    origin: &Coord,
+    tolerance: f64,
+    closed: bool,
+) -> Option<(Coord, Circle)> {
+    let planar: Vec<_> = points.iter().map(|point| project(origin, point)).collect();
+    let circle = fit(&planar, tolerance, closed)?;
+    let bearing = circle.x.atan2(circle.y).to_degrees();
+    let center = geodesy::direct(origin, bearing, circle.x.hypot(circle.y));
+    Some((center, circle))
+}
+
+/// Position of the point in metres east and north of `origin`, in an
+/// azimuthal equidistant projection.
+fn project(origin: &Coord, point: &Coord) -> (f64, f64) {
+    let inverse = geodesy::inverse(origin, point);
+    let bearing = inverse.initial_bearing.to_radians();
+    (
+        inverse.distance * bearing.sin(),
+        inverse.distance * bearing.cos(),
+    )
+}
+
+/// Least squares circle through the points (Kåsa fit), if all points and
+/// edges between them are within the tolerance of the circle, and the
+/// points run around the center in one direction. Closed rings include the
+/// edge from the last to the first point.
+fn fit(points: &[(f64, f64)], tolerance: f64, closed: bool) -> Option<Circle> {
+    let n = points.len() as f64;
+    let (mx, my) = points
+        .iter()
+        .fold((0.0, 0.0), |(x, y), p| (x + p.0 / n, y + p.1 / n));
+    let (mut suu, mut suv, mut svv, mut suuu, mut svvv, mut suvv, mut svuu) =
+        (0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    for &(x, y) in points {
+        let (u, v) = (x - mx, y - my);
+        suu += u * u;
+        suv += u * v;
+        svv += v * v;
+        suuu += u * u * u;
+        svvv += v * v * v;
+        suvv += u * v * v;
+        svuu += v * u * u;
+    }
+    let det = suu * svv - suv * suv;
+    if det.abs() <= f64::EPSILON * suu * svv {
+        return None;
+    }
+    let a = (suuu + suvv) / 2.0;
+    let b = (svvv + svuu) / 2.0;
+    let uc = (a * svv - b * suv) / det;
+    let vc = (b * suu - a * suv) / det;
+    let radius = (uc * uc + vc * vc + (suu + svv) / n).sqrt();
+    let (x, y) = (uc + mx, vc + my);
+    if radius.is_nan() || radius > MAX_RADIUS {
+        return None;
+    }
+
+    let on_circle = |p: &(f64, f64)| ((p.0 - x).hypot(p.1 - y) - radius).abs() <= tolerance;
+    if !points.iter().all(on_circle) {
+        return None;
+    }
+
+    let edges = points.len() - usize::from(!closed);
+    let mut sweep: f64 = 0.0;
+    for i in 0..edges {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        let (ax, ay, bx, by) = (a.0 - x, a.1 - y, b.0 - x, b.1 - y);
+        let angle = (ax * by - ay * bx).atan2(ax * bx + ay * by);
+        // All points must run in the same direction, and the arc between
+        // two points must not bulge out too far from the edge
+        if angle == 0.0
+            || (sweep != 0.0 && angle.signum() != sweep.signum())
+            || radius * (1.0 - (angle / 2.0).cos()) > tolerance
+        {
+            return None;
+        }
+        sweep += angle;
+    }
+    if !closed {
+        // Straight runs are not replaced, and arcs must not overlap themselves
+        let sagitta = radius * (1.0 - (sweep / 2.0).cos());
+        if sweep.abs() >= TAU || (sweep.abs() <= PI && sagitta <= tolerance) {
+            return None;
+        }
+    }
+    Some(Circle {
+        x,
+        y,
+        radius,
+        sweep,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arc_points(center: &Coord, radius: f64, from: f64, to: f64, step: f64) -> Vec<Coord> {
+        let steps = ((to - from) / step).abs().round() as usize;
+        (0..=steps)
+            .map(|i| center.destination(from + (to - from) * i as f64 / steps as f64, radius))
+            .collect()
+    }
+
+    fn polygon(points: Vec<Coord>) -> Geometry {
+        Geometry::Polygon {
+            segments: points.into_iter().map(PolygonSegment::Point).collect(),
+        }
+    }
+
+    #[test]
+    fn circle() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let mut points = arc_points(&center, 5000.0, 0.0, 358.0, 2.0);
+        points.push(points[0]);
+        let mut geom = polygon(points);
+        assert_eq!(geom.fit_arcs(1.0), 180);
+        let Geometry::Circle {
+            centerpoint,
+            radius,
+        } = geom
+        else {
+            panic!("expected circle, got {geom:?}");
+        };
+        assert!(centerpoint.distance_to(&center) < 0.5);
+        assert!((f64::from(radius) * 1852.0 - 5000.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn arcs_in_polygon() {
+        // A pie slice from 0° to 90° followed by a counterclockwise arc back
+        let center = Coord::from_degrees(47.0, 8.0);
+        let mut points = vec![center];
+        points.extend(arc_points(&center, 10_000.0, 0.0, 90.0, 2.0));
+        points.extend(arc_points(&center, 5000.0, 90.0, 0.0, 2.0));
+        let mut geom = polygon(points);
+        assert_eq!(geom.fit_arcs(2.0), 90);
+
+        let Geometry::Polygon { segments } = &geom else {
+            panic!();
+        };
+        let [
+            PolygonSegment::Point(point),
+            PolygonSegment::ArcSegment(outer),
+            PolygonSegment::ArcSegment(inner),
+        ] = &segments[..]
+        else {
+            panic!("unexpected segments {segments:?}");
+        };
+        assert_eq!(*point, center);
+        for (arc, radius, start, end, direction) in [
+            (outer, 10_000.0, 0.0, 90.0, Direction::Cw),
+            (inner, 5000.0, 90.0, 0.0, Direction::Ccw),
+        ] {
+            assert!(arc.centerpoint.distance_to(&center) < 0.5, "{arc:?}");
+            // The radius is rounded to 0.001 NM
+            let radius = (radius / 1852.0 * 1000.0_f64).round() / 1000.0;
+            assert!((f64::from(arc.radius) - radius).abs() < 1e-6, "{arc:?}");
+            let angle_diff = |a: f32, b: f64| geodesy::normalize_longitude(f64::from(a) - b);
+            assert!(angle_diff(arc.angle_start, start).abs() < 1e-3, "{arc:?}");
+            assert!(angle_diff(arc.angle_end, end).abs() < 1e-3, "{arc:?}");
+            assert_eq!(arc.direction, direction);
+        }
+    }
+
+    #[test]
+    fn rounded_arc_within_tolerance() {
+        // Rounding the angles of this arc to 0.01° would move its ends by
+        // 2.6 m, so they are kept
+        let center = Coord::from_degrees(47.0, 8.0);
+        let points = arc_points(&center, 50_000.0, 0.003, 90.003, 0.5);
+        let mut geom = polygon(points.clone());
+        assert_eq!(geom.fit_arcs(0.5), points.len() - 1);
+        let Geometry::Polygon { segments } = &geom else {
+            panic!();
+        };
+        let [PolygonSegment::ArcSegment(arc)] = &segments[..] else {
+            panic!("unexpected segments {segments:?}");
+        };
+        assert!(fits(arc, &points, 0.5), "{arc:?}");
+        assert!((arc.angle_start - 0.003).abs() < 1e-4, "{arc:?}");
+    }
+
+    #[test]
+    fn unchanged() {
+        // Straight lines and corners
+        let polyline = || {
+            polygon(vec![
+                Coord::from_degrees(47.0, 8.0),
+                Coord::from_degrees(47.0, 8.1),
+                Coord::from_degrees(47.0, 8.2),
+                Coord::from_degrees(47.0, 8.3),
+                Coord::from_degrees(47.0, 8.4),
+                Coord::from_degrees(47.0, 8.5),
+                Coord::from_degrees(46.5, 8.5),
+                Coord::from_degrees(46.5, 8.0),
+            ])
+        };
+        let mut geom = polyline();
+        assert_eq!(geom.fit_arcs(10.0), 0);
+        assert_eq!(geom, polyline());
+
+        // Too sparse for the tolerance
+        let center = Coord::from_degrees(47.0, 8.0);
+        let mut geom = polygon(arc_points(&center, 5000.0, 0.0, 350.0, 10.0));
+        assert_eq!(geom.fit_arcs(1.0), 0);
+    }
+
+    #[test]
+    fn tolerance() {
+        // Points rounded to whole seconds need a larger tolerance
+        let center = Coord::from_degrees(47.0, 8.0);
+        let rounded = arc_points(&center, 5000.0, 0.0, 355.0, 5.0)
+            .iter()
+            .map(|p| {
+                let round = |deg: f64| (deg * 3600.0).round() / 3600.0;
+                Coord::from_degrees(round(p.lat()), round(p.lng()))
+            })
+            .collect::<Vec<_>>();
+        let mut geom = polygon(rounded.clone());
+        assert_eq!(geom.fit_arcs(1.0), 0);
+        let mut geom = polygon(rounded);
+        assert_eq!(geom.fit_arcs(25.0), 71);
+        assert!(matches!(geom, Geometry::Circle { .. }));
+    }
+}
@@ -0,0 +1,385 @@
+//! Geodesic calculations on the WGS84 ellipsoid (Vincenty's formulae and
+//! areas).
+//!
+//! Vincenty's inverse formula does not converge for nearly antipodal points.
+//! These fall back to a sphere with the mean earth radius, with an error in
+//! distance of up to 0.5%. Airspace geometries never come close to this.
+
+use crate::Coord;
+
+/// WGS84 semi-major axis in metres.
+pub(crate) const WGS84_A: f64 = 6_378_137.0;
+/// WGS84 flattening.
+pub(crate) const WGS84_F: f64 = 1.0 / 298.257_223_563;
+/// WGS84 semi-minor axis in metres.
+pub(crate) const WGS84_B: f64 = WGS84_A * (1.0 - WGS84_F);
+/// Square of the WGS84 eccentricity.
+const WGS84_E2: f64 = WGS84_F * (2.0 - WGS84_F);
+/// Mean earth radius in metres, used where a spherical model is sufficient.
+pub(crate) const MEAN_RADIUS: f64 = 6_371_008.8;
+
+/// Metres per nautical mile.
+pub(crate) const METRES_PER_NM: f64 = 1852.0;
+
+const EPSILON: f64 = 1e-12;
+const MAX_ITERATIONS: usize = 200;
+
+/// Result of the inverse geodesic problem.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Inverse {
+    /// Distance in metres
+    pub distance: f64,
+    /// Bearing at the start point in degrees (0..360)
+    pub initial_bearing: f64,
+    /// Bearing at the end point in degrees (0..360)
+    pub final_bearing: f64,
+}
+
+/// Distance and bearings between two coordinates.
+pub(crate) fn inverse(from: &Coord, to: &Coord) -> Inverse {
+    inverse_deg(from.lat(), from.lng(), to.lat(), to.lng())
+}
+
+/// The coordinate reached when travelling `distance` metres from `from` on
+/// the given initial bearing (degrees).
+pub(crate) fn direct(from: &Coord, bearing: f64, distance: f64) -> Coord {
+    let (lat, lng) = direct_deg(from.lat(), from.lng(), bearing, distance);
+    Coord::from_degrees(lat, lng)
+}
+
+/// Inverse problem on degrees. Falls back to a spherical model for nearly
+/// antipodal points, where Vincenty's iteration does not converge.
+pub(crate) fn inverse_deg(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> Inverse {
+    vincenty_inverse(lat1, lng1, lat2, lng2)
+        .unwrap_or_else(|| spherical_inverse(lat1, lng1, lat2, lng2))
+}
+
+fn vincenty_inverse(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> Option<Inverse> {
+    let f = WGS84_F;
+    let l = (lng2 - lng1).to_radians();
+    let u1 = ((1.0 - f) * lat1.to_radians().tan()).atan();
+    let u2 = ((1.0 - f) * lat2.to_radians().tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    let mut converged = false;
+    let (mut sin_sigma, mut cos_sigma, mut sigma) = (0.0, 0.0, 0.0);
+    let (mut cos_sq_alpha, mut cos_2sigma_m) = (0.0, 0.0);
+    for _ in 0..MAX_ITERATIONS {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        if sin_sigma == 0.0 {
+            // Coincident points
+            return Some(Inverse {
+                distance: 0.0,
+                initial_bearing: 0.0,
+                final_bearing: 0.0,
+            });
+        }
+        cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+        cos_2sigma_m = if cos_sq_alpha == 0.0 {
+            // Equatorial line
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+        };
+        let c = f / 16.0 * cos_sq_alpha * (4.0 + f * (4.0 - 3.0 * cos_sq_alpha));
+        let previous = lambda;
+        lambda = l
+            + (1.0 - c)
+                * f
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+        if lambda.abs() > std::f64::consts::PI * 1.5 {
+            return None;
+        }
+        if (lambda - previous).abs() < EPSILON {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return None;
+    }
+
+    let u_sq = cos_sq_alpha * (WGS84_A.powi(2) - WGS84_B.powi(2)) / WGS84_B.powi(2);
+    let (a, b) = series_coefficients(u_sq);
+    let delta_sigma = delta_sigma(b, sin_sigma, cos_sigma, cos_2sigma_m);
+    let distance = WGS84_B * a * (sigma - delta_sigma);
+
+    let (sin_lambda, cos_lambda) = lambda.sin_cos();
+    let initial = (cos_u2 * sin_lambda).atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+    let final_ = (cos_u1 * sin_lambda).atan2(-sin_u1 * cos_u2 + cos_u1 * sin_u2 * cos_lambda);
+
+    Some(Inverse {
+        distance,
+        initial_bearing: normalize_bearing(initial.to_degrees()),
+        final_bearing: normalize_bearing(final_.to_degrees()),
+    })
+}
+
+/// Inverse problem on a sphere with the mean earth radius.
+pub(crate) fn spherical_inverse(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> Inverse {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = phi2 - phi1;
+    let d_lambda = (lng2 - lng1).to_radians();
+
+    let h = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    let distance = 2.0 * MEAN_RADIUS * h.sqrt().min(1.0).asin();
+
+    let bearing = |phi1: f64, phi2: f64, d_lambda: f64| {
+        let y = d_lambda.sin() * phi2.cos();
+        let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * d_lambda.cos();
+        normalize_bearing(y.atan2(x).to_degrees())
+    };
+    Inverse {
+        distance,
+        initial_bearing: bearing(phi1, phi2, d_lambda),
+        final_bearing: normalize_bearing(bearing(phi2, phi1, -d_lambda) + 180.0),
+    }
+}
+
+/// Direct problem on degrees, returns (lat, lng).
+pub(crate) fn direct_deg(lat: f64, lng: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    let f = WGS84_F;
+    let (sin_alpha1, cos_alpha1) = bearing.to_radians().sin_cos();
+    let tan_u1 = (1.0 - f) * lat.to_radians().tan();
+    let cos_u1 = 1.0 / (1.0 + tan_u1 * tan_u1).sqrt();
+    let sin_u1 = tan_u1 * cos_u1;
+
+    let sigma1 = tan_u1.atan2(cos_alpha1);
+    let sin_alpha = cos_u1 * sin_alpha1;
+    let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+    let u_sq = cos_sq_alpha * (WGS84_A.powi(2) - WGS84_B.powi(2)) / WGS84_B.powi(2);
+    let (a, b) = series_coefficients(u_sq);
+
+    let sigma0 = distance / (WGS84_B * a);
+    let mut sigma = sigma0;
+    let mut cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+    for _ in 0..MAX_ITERATIONS {
+        cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+        let (sin_sigma, cos_sigma) = sigma.sin_cos();
+        let previous = sigma;
+        sigma = sigma0 + delta_sigma(b, sin_sigma, cos_sigma, cos_2sigma_m);
+        if (sigma - previous).abs() < EPSILON {
+            break;
+        }
+    }
+
+    let (sin_sigma, cos_sigma) = sigma.sin_cos();
+    let tmp = sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1;
+    let lat2 = (sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1)
+        .atan2((1.0 - f) * (sin_alpha * sin_alpha + tmp * tmp).sqrt());
+    let lambda =
+        (sin_sigma * sin_alpha1).atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1);
+    let c = f / 16.0 * cos_sq_alpha * (4.0 + f * (4.0 - 3.0 * cos_sq_alpha));
+    let l = lambda
+        - (1.0 - c)
+            * f
+            * sin_alpha
+            * (sigma
+                + c * sin_sigma
+                    * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+
+    (lat2.to_degrees(), normalize_longitude(lng + l.to_degrees()))
+}
+
+/// Direct problem on a sphere with the mean earth radius, returns (lat, lng).
+pub(crate) fn spherical_direct(lat: f64, lng: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    let (phi1, lambda1) = (lat.to_radians(), lng.to_radians());
+    let (sin_theta, cos_theta) = bearing.to_radians().sin_cos();
+    let delta = distance / MEAN_RADIUS;
+    let (sin_delta, cos_delta) = delta.sin_cos();
+
+    let sin_phi2 = phi1.sin() * cos_delta + phi1.cos() * sin_delta * cos_theta;
+    let phi2 = sin_phi2.clamp(-1.0, 1.0).asin();
+    let lambda2 =
+        lambda1 + (sin_theta * sin_delta * phi1.cos()).atan2(cos_delta - phi1.sin() * sin_phi2);
+    (phi2.to_degrees(), normalize_longitude(lambda2.to_degrees()))
+}
+
+/// The A and B series coefficients shared by both problems.
+fn series_coefficients(u_sq: f64) -> (f64, f64) {
+    let a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+    let b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+    (a, b)
+}
+
+fn delta_sigma(b: f64, sin_sigma: f64, cos_sigma: f64, cos_2sigma_m: f64) -> f64 {
+    b * sin_sigma
+        * (cos_2sigma_m
+            + b / 4.0
+                * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                    - b / 6.0
+                        * cos_2sigma_m
+                        * (-3.0 + 4.0 * sin_sigma.powi(2))
+                        * (-3.0 + 4.0 * cos_2sigma_m.powi(2))))
+}
+
+/// Gaussian radius of curvature (the radius of the sphere that best fits the
+/// ellipsoid locally) at the given latitude in degrees.
+pub(crate) fn gaussian_radius(lat: f64) -> f64 {
+    let sin = lat.to_radians().sin();
+    WGS84_A * (1.0 - WGS84_E2).sqrt() / (1.0 - WGS84_E2 * sin * sin)
+}
+
+/// Area in m² between the equator and the given latitude in degrees, per
+/// radian of longitude.
+pub(crate) fn zone_area(lat: f64) -> f64 {
+    let sin = lat.to_radians().sin();
+    let e = WGS84_E2.sqrt();
+    let q =
+        sin / (1.0 - WGS84_E2 * sin * sin) + ((1.0 + e * sin) / (1.0 - e * sin)).ln() / (2.0 * e);
+    WGS84_B * WGS84_B / 2.0 * q
+}
+
+/// Signed area in m² between an edge and the equator, for an edge that is a
+/// straight line in latitude/longitude. Summed over a closed ring, this is
+/// positive for clockwise rings.
+pub(crate) fn edge_area(from: &Coord, to: &Coord) -> f64 {
+    // 4-point Gauss-Legendre quadrature on 0..1
+    const NODES: [(f64, f64); 4] = [
+        (0.069_431_844_202_973_7, 0.173_927_422_568_726_9),
+        (0.330_009_478_207_571_9, 0.326_072_577_431_273_1),
+        (0.669_990_521_792_428_1, 0.326_072_577_431_273_1),
+        (0.930_568_155_797_026_3, 0.173_927_422_568_726_9),
+    ];
+    let d_lng = normalize_longitude(to.lng() - from.lng());
+    if d_lng == 0.0 {
+        return 0.0;
+    }
+    let d_lat = to.lat() - from.lat();
+    let mean: f64 = NODES
+        .iter()
+        .map(|(t, w)| w * zone_area(from.lat() + t * d_lat))
+        .sum();
+    d_lng.to_radians() * mean
+}
+
+/// Signed area in m² between a geodesic and the equator (see [`edge_area`]).
+pub(crate) fn geodesic_edge_area(from: &Coord, to: &Coord) -> f64 {
+    const STEPS: usize = 16;
+    let inv = inverse(from, to);
+    let mut area = 0.0;
+    let mut prev = *from;
+    for i in 1..=STEPS {
+        let next = if i == STEPS {
+            *to
+        } else {
+            direct(
+                from,
+                inv.initial_bearing,
+                inv.distance * i as f64 / STEPS as f64,
+            )
+        };
+        area += edge_area(&prev, &next);
+        prev = next;
+    }
+    area
+}
+
+/// Normalize a bearing to 0..360 degrees.
+pub(crate) fn normalize_bearing(bearing: f64) -> f64 {
+    let bearing = bearing.rem_euclid(360.0);
+    // rem_euclid may return 360.0 for tiny negative inputs
+    if bearing >= 360.0 { 0.0 } else { bearing }
+}
+
+/// Normalize a longitude to -180..=180 degrees.
+pub(crate) fn normalize_longitude(lng: f64) -> f64 {
+    if (-180.0..=180.0).contains(&lng) {
+        lng
+    } else {
+        (lng + 180.0).rem_euclid(360.0) - 180.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dms(deg: f64, min: f64, sec: f64) -> f64 {
+        deg.signum() * (deg.abs() + min / 60.0 + sec / 3600.0)
+    }
+
+    #[test]
+    fn vincenty_reference() {
+        // Flinders Peak -> Buninyong, Vincenty (1975)
+        let (lat1, lng1) = (-dms(37.0, 57.0, 3.72030), dms(144.0, 25.0, 29.52440));
+        let (lat2, lng2) = (-dms(37.0, 39.0, 10.15610), dms(143.0, 55.0, 35.38390));
+
+        let inv = inverse_deg(lat1, lng1, lat2, lng2);
+        assert!((inv.distance - 54_972.271).abs() < 0.001);
+        assert!((inv.initial_bearing - dms(306.0, 52.0, 5.37)).abs() < 1e-5);
+        assert!((inv.final_bearing - dms(307.0, 10.0, 25.07)).abs() < 1e-5);
+
+        let (lat, lng) = direct_deg(lat1, lng1, inv.initial_bearing, inv.distance);
+        assert!((lat - lat2).abs() < 1e-9);
+        assert!((lng - lng2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn coincident_points() {
+        let inv = inverse_deg(47.0, 8.0, 47.0, 8.0);
+        assert_eq!(inv.distance, 0.0);
+    }
+
+    #[test]
+    fn antipodal_fallback() {
+        // Vincenty does not converge for nearly antipodal points
+        let inv = inverse_deg(0.0, 0.0, 0.5, 179.7);
+        assert!((inv.distance - 19_936_000.0).abs() < 50_000.0);
+    }
+
+    #[test]
+    fn direct_across_antimeridian() {
+        let (lat, lng) = direct_deg(0.0, 179.9, 90.0, 50_000.0);
+        assert!(lat.abs() < 1e-9);
+        assert!((lng - -179.65084).abs() < 1e-4);
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(normalize_bearing(-90.0), 270.0);
+        assert_eq!(normalize_bearing(360.0), 0.0);
+        assert_eq!(normalize_longitude(190.0), -170.0);
+        assert_eq!(normalize_longitude(-180.0), -180.0);
+    }
+
+    #[test]
+    fn edge_area_cell() {
+        // 1° x 1° cell at the equator, clockwise
+        let ring = [
+            Coord::from_degrees(0.0, 0.0),
+            Coord::from_degrees(1.0, 0.0),
+            Coord::from_degrees(1.0, 1.0),
+            Coord::from_degrees(0.0, 1.0),
+        ];
+        let area: f64 = (0..4)
+            .map(|i| edge_area(&ring[i], &ring[(i + 1) % 4]))
+            .sum();
+        assert!((area / 1e6 - 12_308.8).abs() < 0.5, "{area}");
+    }
+
+    #[test]
+    fn radius_of_curvature() {
+        assert!((gaussian_radius(0.0) - 6_356_752.3).abs() < 0.1);
+        assert!((gaussian_radius(90.0) - 6_399_593.6).abs() < 0.1);
+    }
+
+    #[test]
+    fn spherical_roundtrip() {
+        let (lat, lng) = spherical_direct(47.0, 8.0, 60.0, 100_000.0);
+        let inv = spherical_inverse(47.0, 8.0, lat, lng);
+        assert!((inv.distance - 100_000.0).abs() < 1e-6);
+        assert!((inv.initial_bearing - 60.0).abs() < 1e-9);
+    }
+}
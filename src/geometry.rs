@@ -1,6 +1,6 @@
 use std::fmt;
 
-use crate::Coord;
+use crate::{BoundingBox, Coord, geodesy};
 
 /// Arc direction, either clockwise or counterclockwise.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -27,6 +27,14 @@ impl Direction {
             _ => Err(format!("Invalid direction: {}", data)),
         }
     }
+
+    /// The opposite direction.
+    pub(crate) fn reversed(self) -> Self {
+        match self {
+            Self::Cw => Self::Ccw,
+            Self::Ccw => Self::Cw,
+        }
+    }
 }
 
 /// An arc segment (DA record).
@@ -61,6 +69,113 @@ pub enum PolygonSegment {
     ArcSegment(ArcSegment),
 }
 
+/// Precision of the radius of computed arcs in NM (about 2 m).
+const RADIUS_PRECISION: f64 = 0.001;
+
+/// Precision of the angles of computed arcs in degrees.
+const ANGLE_PRECISION: f64 = 0.01;
+
+/// Round an angle in degrees to 0.01°, keeping angles below 360° below 360°.
+pub(crate) fn round_angle(angle: f64) -> f32 {
+    let rounded = (angle / ANGLE_PRECISION).round() * ANGLE_PRECISION;
+    if angle < 360.0 && rounded >= 360.0 {
+        (rounded - 360.0) as f32
+    } else {
+        rounded as f32
+    }
+}
+
+impl ArcSegment {
+    /// Convert to the `DB` form, with start and end points computed
+    /// geodesically from the radius and angles.
+    pub fn to_arc(&self) -> Arc {
+        let edge = ArcEdge::from_arc_segment(self);
+        Arc {
+            centerpoint: self.centerpoint,
+            start: edge.start_point,
+            end: edge.end_point,
+            direction: self.direction,
+        }
+    }
+
+    /// Round the radius to 0.001 NM and the angles to 0.01°, so that arcs
+    /// computed from other values are written without floating point noise.
+    pub(crate) fn rounded(&self) -> Self {
+        let radius = (f64::from(self.radius) / RADIUS_PRECISION).round() * RADIUS_PRECISION;
+        Self {
+            radius: radius as f32,
+            angle_start: round_angle(f64::from(self.angle_start)),
+            angle_end: round_angle(f64::from(self.angle_end)),
+            ..*self
+        }
+    }
+}
+
+impl Arc {
+    /// Convert to the `DA` form. The angles are the bearings from the
+    /// centerpoint to the start and end points. If the points are at
+    /// different distances from the centerpoint (see
+    /// [`Self::radius_mismatch`]), their mean distance is used as radius.
+    pub fn to_arc_segment(&self) -> ArcSegment {
+        let start = geodesy::inverse(&self.centerpoint, &self.start);
+        let end = geodesy::inverse(&self.centerpoint, &self.end);
+        let radius = (start.distance + end.distance) / 2.0 / geodesy::METRES_PER_NM;
+        ArcSegment {
+            centerpoint: self.centerpoint,
+            radius: radius as f32,
+            angle_start: start.initial_bearing as f32,
+            angle_end: end.initial_bearing as f32,
+            direction: self.direction,
+        }
+    }
+
+    /// Returns the difference in metres between the distances of the start
+    /// and end points from the centerpoint.
+    ///
+    /// Many files contain `DB` records with slightly inconsistent points.
+    /// Large values usually indicate a wrong centerpoint.
+    pub fn radius_mismatch(&self) -> f64 {
+        let start = geodesy::inverse(&self.centerpoint, &self.start).distance;
+        let end = geodesy::inverse(&self.centerpoint, &self.end).distance;
+        (start - end).abs()
+    }
+}
+
+impl PolygonSegment {
+    /// The first point of the segment.
+    pub(crate) fn start_point(&self) -> Coord {
+        match self {
+            Self::Point(coord) => *coord,
+            Self::Arc(arc) => arc.start,
+            Self::ArcSegment(arc) => ArcEdge::from_arc_segment(arc).start_point,
+        }
+    }
+
+    /// The last point of the segment.
+    pub(crate) fn end_point(&self) -> Coord {
+        match self {
+            Self::Point(coord) => *coord,
+            Self::Arc(arc) => arc.end,
+            Self::ArcSegment(arc) => ArcEdge::from_arc_segment(arc).end_point,
+        }
+    }
+
+    /// Reverse the direction in which the segment is traversed.
+    pub(crate) fn reverse(&mut self) {
+        match self {
+            Self::Point(_) => {}
+            Self::Arc(arc) => {
+                std::mem::swap(&mut arc.start, &mut arc.end);
+                arc.direction = arc.direction.reversed();
+            }
+            Self::ArcSegment(arc) => {
+                std::mem::swap(&mut arc.angle_start, &mut arc.angle_end);
+                arc.direction = arc.direction.reversed();
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type"))]
@@ -87,3 +202,1407 @@ impl fmt::Display for Geometry {
         }
     }
 }
+
+/// Smallest tolerance accepted by [`Geometry::to_points`], in metres.
+const MIN_TOLERANCE: f64 = 0.01;
+
+impl Geometry {
+    /// Flatten the geometry into a closed ring of points.
+    ///
+    /// Circles and arcs are interpolated geodesically on the WGS84 ellipsoid,
+    /// so that the distance between the true arc and the chords between the
+    /// returned points is at most `tolerance` metres (clamped to at least
+    /// 1 cm). Circles start north of the centerpoint and run clockwise, arcs
+    /// follow their [`Direction`]. Arcs with equal start and end angle are
+    /// treated as full circles.
+    ///
+    /// The first and the last point of the ring are equal. An empty polygon
+    /// returns an empty list.
+    pub fn to_points(&self, tolerance: f64) -> Vec<Coord> {
+        let tolerance = if tolerance.is_nan() {
+            MIN_TOLERANCE
+        } else {
+            tolerance.max(MIN_TOLERANCE)
+        };
+        let mut ring = Ring::default();
+        match self {
+            Self::Circle {
+                centerpoint,
+                radius,
+            } => {
+                let radius = f64::from(*radius) * geodesy::METRES_PER_NM;
+                let steps = arc_steps(radius, 360.0, tolerance).max(4);
+                for i in 0..steps {
+                    let bearing = 360.0 * i as f64 / steps as f64;
+                    ring.push(geodesy::direct(centerpoint, bearing, radius));
+                }
+            }
+            Self::Polygon { segments } => {
+                for segment in segments {
+                    match segment {
+                        PolygonSegment::Point(coord) => ring.push(*coord),
+                        PolygonSegment::ArcSegment(arc) => {
+                            ring.push_arc(&ArcEdge::from_arc_segment(arc), tolerance);
+                        }
+                        PolygonSegment::Arc(arc) => {
+                            ring.push_arc(&ArcEdge::from_arc(arc), tolerance);
+                        }
+                    }
+                }
+            }
+        }
+        ring.close()
+    }
+
+    /// Flatten the geometry like [`Self::to_points`], split into rings that
+    /// do not cross the antimeridian, as recommended for GeoJSON (RFC 7946).
+    ///
+    /// Rings are cut at ±180° longitude, and rings around a pole are closed
+    /// along the pole. Geometries not crossing the antimeridian return a
+    /// single ring. Polygons that cross the antimeridian more than twice
+    /// may result in parts connected along the antimeridian.
+    pub fn to_rings(&self, tolerance: f64) -> Vec<Vec<Coord>> {
+        let points = self.to_points(tolerance);
+        if points.is_empty() {
+            return vec![];
+        }
+        let planar = planar_ring(&points[..points.len() - 1]);
+        let (min, max) = planar
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(x, _)| {
+                (min.min(x), max.max(x))
+            });
+        if planar.len() < 3 || (min >= -180.0 && max <= 180.0) {
+            return vec![points];
+        }
+
+        // Clip to every 360° window [-180, 180] + k * 360 that the unwrapped
+        // ring overlaps, and shift the parts back
+        let first = ((min - 180.0) / 360.0).ceil() as i32;
+        let last = ((max + 180.0) / 360.0).floor() as i32;
+        let mut rings = Vec::new();
+        for k in first..=last {
+            let offset = 360.0 * f64::from(k);
+            let clipped = clip_ring(&planar, offset - 180.0, true);
+            let clipped = clip_ring(&clipped, offset + 180.0, false);
+            let mut ring = Ring::default();
+            for (lng, lat) in clipped {
+                ring.push(Coord::from_degrees(lat, lng - offset));
+            }
+            let ring = ring.close();
+            // At least three distinct points
+            if ring.len() >= 4 {
+                rings.push(ring);
+            }
+        }
+        rings
+    }
+}
+
+/// Builds a ring of points, skipping consecutive duplicates.
+#[derive(Default)]
+struct Ring {
+    points: Vec<Coord>,
+}
+
+impl Ring {
+    fn push(&mut self, coord: Coord) {
+        if self.points.last() != Some(&coord) {
+            self.points.push(coord);
+        }
+    }
+
+    /// Interpolate an arc, including its end points.
+    fn push_arc(&mut self, edge: &ArcEdge, tolerance: f64) {
+        let steps = arc_steps(
+            edge.start_radius.max(edge.end_radius),
+            edge.sweep(),
+            tolerance,
+        );
+        self.push(edge.start_point);
+        for i in 1..steps {
+            let (bearing, radius) = edge.at(i as f64 / steps as f64);
+            self.push(geodesy::direct(&edge.center, bearing, radius));
+        }
+        self.push(edge.end_point);
+    }
+
+    fn close(mut self) -> Vec<Coord> {
+        if let Some(&first) = self.points.first()
+            && self.points.last() != Some(&first)
+        {
+            self.points.push(first);
+        }
+        self.points
+    }
+}
+
+/// Number of chords needed so that the sagitta of every chord of an arc with
+/// the given radius (metres) and sweep (degrees) is at most `tolerance`.
+fn arc_steps(radius: f64, sweep: f64, tolerance: f64) -> usize {
+    if radius <= tolerance {
+        return 1;
+    }
+    // Sagitta s = r * (1 - cos(θ / 2))
+    let max_angle = 2.0 * (1.0 - tolerance / radius).acos();
+    (sweep.to_radians() / max_angle).ceil().max(1.0) as usize
+}
+
+impl Geometry {
+    /// Returns true if the coordinate lies inside the geometry.
+    ///
+    /// Circles are tested against the geodesic distance to the centerpoint.
+    /// Polygons are tested exactly, including their arc edges: straight
+    /// edges are straight lines in latitude/longitude, arcs follow the
+    /// geodesic circle around their centerpoint (see [`Self::to_points`]).
+    /// Points on the boundary may be reported either way.
+    pub fn contains(&self, coord: &Coord) -> bool {
+        match self {
+            Self::Circle {
+                centerpoint,
+                radius,
+            } => {
+                let radius = f64::from(*radius) * geodesy::METRES_PER_NM;
+                geodesy::inverse(centerpoint, coord).distance <= radius
+            }
+            Self::Polygon { segments } => {
+                // The area is the polygon through all vertices and arc end
+                // points ("chord polygon"), with the circular segments between
+                // each arc and its chord added or cut out. Using the even-odd
+                // rule, both cases are handled by toggling the result.
+                let mut chords = Vec::with_capacity(segments.len() * 2);
+                let mut inside = false;
+                for segment in segments {
+                    let edge = match segment {
+                        PolygonSegment::Point(point) => {
+                            chords.push(*point);
+                            continue;
+                        }
+                        PolygonSegment::ArcSegment(arc) => ArcEdge::from_arc_segment(arc),
+                        PolygonSegment::Arc(arc) => ArcEdge::from_arc(arc),
+                    };
+                    chords.extend([edge.start_point, edge.end_point]);
+                    inside ^= edge.segment_contains(coord);
+                }
+                inside ^ ring_contains(&chords, coord)
+            }
+        }
+    }
+}
+
+impl Geometry {
+    /// Returns the bounding box of the geometry, or `None` for an empty
+    /// polygon.
+    ///
+    /// The full extent of circles and arcs is included, not just their end
+    /// points. Geometries enclosing a pole extend to that pole and span all
+    /// longitudes.
+    pub fn bbox(&self) -> Option<BoundingBox> {
+        match self {
+            Self::Circle {
+                centerpoint,
+                radius,
+            } => {
+                let radius = f64::from(*radius) * geodesy::METRES_PER_NM;
+                if let Some(pole) = circle_enclosed_pole(centerpoint, radius) {
+                    let away = if pole > 0.0 { 180.0 } else { 0.0 };
+                    let (lat, _) =
+                        geodesy::direct_deg(centerpoint.lat(), centerpoint.lng(), away, radius);
+                    return Some(BoundingBox {
+                        min_lat: lat.min(pole),
+                        min_lng: -180.0,
+                        max_lat: lat.max(pole),
+                        max_lng: 180.0,
+                    });
+                }
+                let mut bbox = BoundingBox::from_coord(centerpoint);
+                for extreme in circle_extremes(centerpoint, radius) {
+                    bbox.extend_degrees(extreme.lat, extreme.lng);
+                }
+                Some(bbox)
+            }
+            Self::Polygon { segments } => {
+                let mut bbox: Option<BoundingBox> = None;
+                for segment in segments {
+                    let edge = match segment {
+                        PolygonSegment::Point(point) => {
+                            extend_bbox(&mut bbox, point.lat(), point.lng());
+                            continue;
+                        }
+                        PolygonSegment::ArcSegment(arc) => ArcEdge::from_arc_segment(arc),
+                        PolygonSegment::Arc(arc) => ArcEdge::from_arc(arc),
+                    };
+                    for point in [&edge.start_point, &edge.end_point] {
+                        extend_bbox(&mut bbox, point.lat(), point.lng());
+                    }
+                    // Use the larger radius for arcs with different start and
+                    // end radii, so that the extent is never underestimated
+                    let radius = edge.start_radius.max(edge.end_radius);
+                    if circle_enclosed_pole(&edge.center, radius).is_some() {
+                        // The extremes of arcs around a pole are not well
+                        // defined, use the flattened arc instead
+                        let mut ring = Ring::default();
+                        ring.push_arc(&edge, BBOX_TOLERANCE);
+                        for point in ring.points {
+                            extend_bbox(&mut bbox, point.lat(), point.lng());
+                        }
+                        continue;
+                    }
+                    for extreme in circle_extremes(&edge.center, radius) {
+                        if edge.offset(extreme.bearing) <= edge.sweep() {
+                            extend_bbox(&mut bbox, extreme.lat, extreme.lng);
+                        }
+                    }
+                }
+                let ring: Vec<_> = chord_polygon(segments)
+                    .0
+                    .into_iter()
+                    .map(|(point, _)| point)
+                    .collect();
+                let (_, winding) = unwrap_longitudes(&ring);
+                if let (Some(bbox), Some(pole)) = (&mut bbox, enclosed_pole(&ring, winding)) {
+                    bbox.extend_degrees(pole, 0.0);
+                    bbox.min_lng = -180.0;
+                    bbox.max_lng = 180.0;
+                }
+                bbox
+            }
+        }
+    }
+}
+
+/// Tolerance in metres for flattening arcs around a pole in
+/// [`Geometry::bbox`].
+const BBOX_TOLERANCE: f64 = 10.0;
+
+/// Square metres per square nautical mile.
+const M2_PER_NM2: f64 = geodesy::METRES_PER_NM * geodesy::METRES_PER_NM;
+
+impl Geometry {
+    /// Returns the area of the geometry on the WGS84 ellipsoid in m².
+    ///
+    /// Straight edges are straight lines in latitude/longitude (see
+    /// [`Self::contains`]) and are integrated on the ellipsoid. Circles and
+    /// the circular segments of arcs use closed formulas on the sphere that
+    /// best fits the ellipsoid at their centerpoint, which is accurate to
+    /// well below 0.1% for airspace-sized radii. Self-intersecting polygons
+    /// give meaningless results.
+    pub fn area_m2(&self) -> f64 {
+        match self {
+            Self::Circle {
+                centerpoint,
+                radius,
+            } => {
+                let radius = f64::from(*radius) * geodesy::METRES_PER_NM;
+                let r = geodesy::gaussian_radius(centerpoint.lat());
+                2.0 * std::f64::consts::PI * r * r * (1.0 - (radius / r).cos())
+            }
+            Self::Polygon { segments } => signed_area(segments).abs(),
+        }
+    }
+
+    /// Returns the area of the geometry in NM² (see [`Self::area_m2`]).
+    pub fn area_nm2(&self) -> f64 {
+        self.area_m2() / M2_PER_NM2
+    }
+
+    /// Returns the length of the boundary of the geometry in metres.
+    ///
+    /// Straight edges are measured along the geodesic between their end
+    /// points, circles and arcs along the geodesic circle around their
+    /// centerpoint. Polygons are implicitly closed.
+    pub fn perimeter_m(&self) -> f64 {
+        match self {
+            Self::Circle {
+                centerpoint,
+                radius,
+            } => {
+                let radius = f64::from(*radius) * geodesy::METRES_PER_NM;
+                let r = geodesy::gaussian_radius(centerpoint.lat());
+                2.0 * std::f64::consts::PI * r * (radius / r).sin()
+            }
+            Self::Polygon { segments } => {
+                let (chords, arcs) = chord_polygon(segments);
+                let straight: f64 = (0..chords.len())
+                    .filter(|&i| !chords[i].1)
+                    .map(|i| {
+                        geodesy::inverse(&chords[i].0, &chords[(i + 1) % chords.len()].0).distance
+                    })
+                    .sum();
+                straight + arcs.iter().map(ArcEdge::length).sum::<f64>()
+            }
+        }
+    }
+
+    /// Returns the length of the boundary of the geometry in NM (see
+    /// [`Self::perimeter_m`]).
+    pub fn perimeter_nm(&self) -> f64 {
+        self.perimeter_m() / geodesy::METRES_PER_NM
+    }
+}
+
+/// Area of a polygon in m², positive for clockwise and negative for
+/// counterclockwise rings.
+pub(crate) fn signed_area(segments: &[PolygonSegment]) -> f64 {
+    let (chords, arcs) = chord_polygon(segments);
+    let mut area = 0.0;
+    let ring: Vec<_> = chords.iter().map(|&(point, _)| point).collect();
+    let (_, winding) = unwrap_longitudes(&ring);
+    if let Some(pole) = enclosed_pole(&ring, winding) {
+        // The edges sum up to the area between the ring and the equator,
+        // measure the area between the ring and the pole instead
+        area -= winding.to_radians() * geodesy::zone_area(pole);
+    }
+    for (i, &(from, is_arc)) in chords.iter().enumerate() {
+        let to = &chords[(i + 1) % chords.len()].0;
+        // Arc segment areas are measured from the geodesic chord
+        area += if is_arc {
+            geodesy::geodesic_edge_area(&from, to)
+        } else {
+            geodesy::edge_area(&from, to)
+        };
+    }
+    // The chord polygon area is positive for clockwise rings, so clockwise
+    // arcs add their segment and counterclockwise arcs cut it out, regardless
+    // of the ring orientation.
+    for edge in &arcs {
+        area += match edge.direction {
+            Direction::Cw => edge.segment_area(),
+            Direction::Ccw => -edge.segment_area(),
+        };
+    }
+    area
+}
+
+/// Flatten polygon segments like [`Geometry::to_points`], without closing
+/// the ring. Every point is paired with the index of the segment it belongs
+/// to.
+pub(crate) fn boundary(segments: &[PolygonSegment], tolerance: f64) -> Vec<(Coord, usize)> {
+    let mut points = Vec::with_capacity(segments.len());
+    for (index, segment) in segments.iter().enumerate() {
+        let edge = match segment {
+            PolygonSegment::Point(point) => {
+                points.push((*point, index));
+                continue;
+            }
+            PolygonSegment::ArcSegment(arc) => ArcEdge::from_arc_segment(arc),
+            PolygonSegment::Arc(arc) => ArcEdge::from_arc(arc),
+        };
+        let mut ring = Ring::default();
+        ring.push_arc(&edge, tolerance);
+        points.extend(ring.points.into_iter().map(|point| (point, index)));
+    }
+    points
+}
+
+/// Splits polygon segments into the polygon through all vertices and arc end
+/// points and the arc edges. Every vertex is paired with a flag that is true
+/// if the edge to the next vertex is the chord of an arc.
+fn chord_polygon(segments: &[PolygonSegment]) -> (Vec<(Coord, bool)>, Vec<ArcEdge>) {
+    let mut chords = Vec::with_capacity(segments.len() * 2);
+    let mut arcs = Vec::new();
+    for segment in segments {
+        let edge = match segment {
+            PolygonSegment::Point(point) => {
+                chords.push((*point, false));
+                continue;
+            }
+            PolygonSegment::ArcSegment(arc) => ArcEdge::from_arc_segment(arc),
+            PolygonSegment::Arc(arc) => ArcEdge::from_arc(arc),
+        };
+        chords.extend([(edge.start_point, true), (edge.end_point, false)]);
+        arcs.push(edge);
+    }
+    (chords, arcs)
+}
+
+/// A point of a circle with extreme latitude or longitude.
+struct Extreme {
+    /// Bearing from the center
+    bearing: f64,
+    lat: f64,
+    lng: f64,
+}
+
+fn extend_bbox(bbox: &mut Option<BoundingBox>, lat: f64, lng: f64) {
+    match bbox {
+        Some(bbox) => bbox.extend_degrees(lat, lng),
+        None => *bbox = Some(BoundingBox::from_degrees(lat, lng)),
+    }
+}
+
+/// The pole enclosed by a geodesic circle, if any.
+fn circle_enclosed_pole(center: &Coord, radius: f64) -> Option<f64> {
+    [90.0, -90.0].into_iter().find(|&pole| {
+        geodesy::inverse_deg(center.lat(), center.lng(), pole, center.lng()).distance <= radius
+    })
+}
+
+/// The northern, southern, eastern and western extremes of a geodesic circle
+/// that does not enclose a pole.
+fn circle_extremes(center: &Coord, radius: f64) -> Vec<Extreme> {
+    let (lat, lng) = (center.lat(), center.lng());
+    let at = |bearing: f64| {
+        let (lat, lng) = geodesy::direct_deg(lat, lng, bearing, radius);
+        Extreme { bearing, lat, lng }
+    };
+
+    // Longitude offset from the center, unwrapped across the antimeridian
+    let offset = |bearing: f64| {
+        let (_, point_lng) = geodesy::direct_deg(lat, lng, bearing, radius);
+        geodesy::normalize_longitude(point_lng - lng)
+    };
+    let east = golden_section_max(0.0, 180.0, offset);
+    let west = golden_section_max(180.0, 360.0, |b| -offset(b));
+    vec![at(0.0), at(180.0), at(east), at(west)]
+}
+
+/// Find the maximum of a unimodal function on `[a, b]`.
+fn golden_section_max(mut a: f64, mut b: f64, f: impl Fn(f64) -> f64) -> f64 {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut c = b - ratio * (b - a);
+    let mut d = a + ratio * (b - a);
+    let (mut fc, mut fd) = (f(c), f(d));
+    while b - a > 1e-9 {
+        if fc > fd {
+            b = d;
+            (d, fd) = (c, fc);
+            c = b - ratio * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            (c, fc) = (d, fd);
+            d = a + ratio * (b - a);
+            fd = f(d);
+        }
+    }
+    (a + b) / 2.0
+}
+
+/// An arc edge described by bearings (degrees) and radii (metres) around a
+/// center. The radius changes linearly with the bearing.
+struct ArcEdge {
+    center: Coord,
+    start: f64,
+    start_radius: f64,
+    start_point: Coord,
+    end: f64,
+    end_radius: f64,
+    end_point: Coord,
+    direction: Direction,
+}
+
+impl ArcEdge {
+    fn from_arc_segment(arc: &ArcSegment) -> Self {
+        let radius = f64::from(arc.radius) * geodesy::METRES_PER_NM;
+        let (start, end) = (f64::from(arc.angle_start), f64::from(arc.angle_end));
+        Self {
+            center: arc.centerpoint,
+            start,
+            start_radius: radius,
+            start_point: geodesy::direct(&arc.centerpoint, start, radius),
+            end,
+            end_radius: radius,
+            end_point: geodesy::direct(&arc.centerpoint, end, radius),
+            direction: arc.direction,
+        }
+    }
+
+    fn from_arc(arc: &Arc) -> Self {
+        let start = geodesy::inverse(&arc.centerpoint, &arc.start);
+        let end = geodesy::inverse(&arc.centerpoint, &arc.end);
+        Self {
+            center: arc.centerpoint,
+            start: start.initial_bearing,
+            start_radius: start.distance,
+            start_point: arc.start,
+            end: end.initial_bearing,
+            end_radius: end.distance,
+            end_point: arc.end,
+            direction: arc.direction,
+        }
+    }
+
+    /// Angle swept by the arc in degrees (0..=360).
+    fn sweep(&self) -> f64 {
+        let sweep = match self.direction {
+            Direction::Cw => (self.end - self.start).rem_euclid(360.0),
+            Direction::Ccw => (self.start - self.end).rem_euclid(360.0),
+        };
+        if sweep == 0.0 { 360.0 } else { sweep }
+    }
+
+    /// Bearing and radius at the fraction `t` (0..=1) of the arc.
+    fn at(&self, t: f64) -> (f64, f64) {
+        let sign = match self.direction {
+            Direction::Cw => 1.0,
+            Direction::Ccw => -1.0,
+        };
+        let bearing = self.start + sign * self.sweep() * t;
+        let radius = self.start_radius + (self.end_radius - self.start_radius) * t;
+        (bearing, radius)
+    }
+
+    /// Offset of a bearing from the start of the arc in the arc direction
+    /// (0..360).
+    fn offset(&self, bearing: f64) -> f64 {
+        match self.direction {
+            Direction::Cw => (bearing - self.start).rem_euclid(360.0),
+            Direction::Ccw => (self.start - bearing).rem_euclid(360.0),
+        }
+    }
+
+    /// Returns true if the coordinate lies between the arc and its chord.
+    fn segment_contains(&self, coord: &Coord) -> bool {
+        let sweep = self.sweep();
+        let inv = geodesy::inverse(&self.center, coord);
+        let offset = self.offset(inv.initial_bearing);
+        let in_wedge = offset <= sweep;
+
+        // The circular segment is the sector XOR the triangle (center, start,
+        // end): for arcs below 180°, the triangle is part of the sector, for
+        // larger arcs it lies in the remaining wedge.
+        let (_, radius) = self.at(offset / sweep);
+        let in_sector = in_wedge && inv.distance <= radius;
+        let in_triangle_wedge = if sweep < 180.0 { in_wedge } else { !in_wedge };
+        let (start, end) = (&self.start_point, &self.end_point);
+        let in_triangle = sweep < 360.0
+            && in_triangle_wedge
+            && side(start, end, coord) * side(start, end, &self.center) > 0.0;
+        in_sector ^ in_triangle
+    }
+
+    /// Signed change of the radius per radian of bearing.
+    fn radius_slope(&self) -> f64 {
+        (self.end_radius - self.start_radius) / self.sweep().to_radians()
+    }
+
+    /// Length of the arc in metres, on the sphere that best fits the
+    /// ellipsoid at the center.
+    fn length(&self) -> f64 {
+        let r = geodesy::gaussian_radius(self.center.lat());
+        let sweep = self.sweep().to_radians();
+        if self.start_radius == self.end_radius {
+            return sweep * r * (self.start_radius / r).sin();
+        }
+        // ds² = (R sin(ρ/R) dθ)² + dρ², integrated with Simpson's rule
+        const STEPS: usize = 64;
+        let slope = self.radius_slope();
+        let f = |t: f64| {
+            let (_, radius) = self.at(t);
+            (r * (radius / r).sin()).hypot(slope)
+        };
+        let sum: f64 = (0..=STEPS)
+            .map(|i| {
+                let weight = match i {
+                    0 | STEPS => 1.0,
+                    i if i % 2 == 1 => 4.0,
+                    _ => 2.0,
+                };
+                weight * f(i as f64 / STEPS as f64)
+            })
+            .sum();
+        sum * sweep / (3.0 * STEPS as f64)
+    }
+
+    /// Area in m² between the arc and its chord, on the sphere that best fits
+    /// the ellipsoid at the center.
+    fn segment_area(&self) -> f64 {
+        let r = geodesy::gaussian_radius(self.center.lat());
+        let sweep = self.sweep();
+        let theta = sweep.to_radians();
+        let sector = if self.start_radius == self.end_radius {
+            theta * r * r * (1.0 - (self.start_radius / r).cos())
+        } else {
+            // ∫ R² (1 - cos(ρ(θ)/R)) dθ with ρ changing linearly
+            let slope = self.radius_slope();
+            let sin_diff = (self.end_radius / r).sin() - (self.start_radius / r).sin();
+            r * r * (theta - r / slope * sin_diff)
+        };
+        if sweep == 360.0 {
+            return sector;
+        }
+        // Spherical excess of the triangle (center, start, end)
+        let angle = sweep.min(360.0 - sweep).to_radians();
+        let tan_product = (self.start_radius / r / 2.0).tan() * (self.end_radius / r / 2.0).tan();
+        let excess = 2.0 * (tan_product * angle.sin()).atan2(1.0 + tan_product * angle.cos());
+        let triangle = excess * r * r;
+        if sweep <= 180.0 {
+            sector - triangle
+        } else {
+            sector + triangle
+        }
+    }
+}
+
+/// Sign of the cross product (b - a) × (p - a) in latitude/longitude, using
+/// the shorter way around for longitude differences.
+fn side(a: &Coord, b: &Coord, p: &Coord) -> f64 {
+    let d_lng = |from: &Coord, to: &Coord| geodesy::normalize_longitude(to.lng() - from.lng());
+    d_lng(a, b) * (p.lat() - a.lat()) - (b.lat() - a.lat()) * d_lng(a, p)
+}
+
+/// Even-odd ray casting test for an implicitly closed ring in
+/// latitude/longitude, handling rings across the antimeridian or around a
+/// pole (see [`planar_ring`]).
+fn ring_contains(ring: &[Coord], coord: &Coord) -> bool {
+    let planar = planar_ring(ring);
+    let (min, max) = planar
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(x, _)| {
+            (min.min(x), max.max(x))
+        });
+    // Test every copy of the coordinate within the unwrapped longitude range
+    let mut x = coord.lng() + 360.0 * ((min - coord.lng()) / 360.0).ceil();
+    while x <= max {
+        if planar_contains(&planar, x, coord.lat()) {
+            return true;
+        }
+        x += 360.0;
+    }
+    false
+}
+
+/// Even-odd ray casting test for an implicitly closed ring of (x, y) points.
+pub(crate) fn planar_contains(ring: &[(f64, f64)], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    let mut j = ring.len().wrapping_sub(1);
+    for (i, &(ax, ay)) in ring.iter().enumerate() {
+        let (bx, by) = ring[j];
+        if (ay > y) != (by > y) && x < (bx - ax) * (y - ay) / (by - ay) + ax {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Distance from `p` to the segment from `a` to `b`.
+pub(crate) fn segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared > 0.0 {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (p.0 - a.0 - t * dx).hypot(p.1 - a.1 - t * dy)
+}
+
+/// Unwrap the longitudes of an implicitly closed ring, so that consecutive
+/// points never differ by more than 180°. Returns the unwrapped longitudes
+/// and the total change in longitude around the ring, which is 0 or ±360 for
+/// rings around a pole.
+fn unwrap_longitudes(ring: &[Coord]) -> (Vec<f64>, f64) {
+    let (Some(first), Some(last)) = (ring.first(), ring.last()) else {
+        return (vec![], 0.0);
+    };
+    let mut lngs = Vec::with_capacity(ring.len());
+    let mut lng = first.lng();
+    lngs.push(lng);
+    for pair in ring.windows(2) {
+        lng += geodesy::normalize_longitude(pair[1].lng() - pair[0].lng());
+        lngs.push(lng);
+    }
+    let winding = lng + geodesy::normalize_longitude(first.lng() - last.lng()) - first.lng();
+    (lngs, winding)
+}
+
+/// The pole enclosed by a ring with the given winding (see
+/// [`unwrap_longitudes`]), if any.
+///
+/// A ring around a pole divides the earth into two parts, each containing
+/// one pole. The part on the same side of the equator as the average
+/// latitude of the ring is considered enclosed.
+fn enclosed_pole(ring: &[Coord], winding: f64) -> Option<f64> {
+    if winding.abs() < 180.0 {
+        return None;
+    }
+    let mean = ring.iter().map(Coord::lat).sum::<f64>() / ring.len() as f64;
+    Some(if mean >= 0.0 { 90.0 } else { -90.0 })
+}
+
+/// Convert an implicitly closed ring to (unwrapped longitude, latitude)
+/// points. Rings around a pole are closed along the pole, so that the result
+/// is a simple polygon spanning 360° of longitude.
+pub(crate) fn planar_ring(ring: &[Coord]) -> Vec<(f64, f64)> {
+    let (lngs, winding) = unwrap_longitudes(ring);
+    let mut planar: Vec<_> = lngs
+        .iter()
+        .zip(ring)
+        .map(|(&lng, p)| (lng, p.lat()))
+        .collect();
+    if let Some(pole) = enclosed_pole(ring, winding) {
+        let (first, lat) = (lngs[0], ring[0].lat());
+        planar.push((first + winding, lat));
+        // Split the edge along the pole, so that no edge spans 180° or more
+        for i in 0..=4 {
+            planar.push((first + winding * f64::from(4 - i) / 4.0, pole));
+        }
+    }
+    planar
+}
+
+/// Clip a planar ring to the half plane `x >= limit` (or `x <= limit`),
+/// using the Sutherland–Hodgman algorithm.
+fn clip_ring(ring: &[(f64, f64)], limit: f64, keep_greater: bool) -> Vec<(f64, f64)> {
+    let inside = |&(x, _): &(f64, f64)| if keep_greater { x >= limit } else { x <= limit };
+    let mut clipped = Vec::with_capacity(ring.len());
+    for (i, a) in ring.iter().enumerate() {
+        let b = &ring[(i + 1) % ring.len()];
+        if inside(a) {
+            clipped.push(*a);
+        }
+        if inside(a) != inside(b) {
+            let t = (limit - a.0) / (b.0 - a.0);
+            clipped.push((limit, a.1 + (b.1 - a.1) * t));
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Orientation;
+
+    fn assert_ring_within(points: &[Coord], center: &Coord, radius: f64, tolerance: f64) {
+        for pair in points.windows(2) {
+            for point in pair {
+                let d = geodesy::inverse(center, point).distance;
+                assert!(
+                    (d - radius).abs() < 0.05,
+                    "vertex at {d} m, expected {radius} m"
+                );
+            }
+            // Chord midpoint must not deviate more than the tolerance
+            let mid = Coord::from_degrees(
+                (pair[0].lat() + pair[1].lat()) / 2.0,
+                (pair[0].lng() + pair[1].lng()) / 2.0,
+            );
+            let d = geodesy::inverse(center, &mid).distance;
+            assert!(radius - d <= tolerance * 1.01, "sagitta {} m", radius - d);
+        }
+    }
+
+    #[test]
+    fn circle_to_points() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let geom = Geometry::Circle {
+            centerpoint: center,
+            radius: 5.0,
+        };
+        let points = geom.to_points(10.0);
+
+        assert_eq!(points.first(), points.last());
+        assert_eq!(points.len(), 69);
+        assert_ring_within(&points, &center, 5.0 * 1852.0, 10.0);
+
+        // Starts north of the center, clockwise
+        assert!(points[0].lat() > center.lat());
+        assert!(points[1].lng() > center.lng());
+
+        // Finer tolerance, more points
+        assert!(geom.to_points(1.0).len() > points.len());
+        // Tiny circles still form a polygon
+        assert_eq!(geom.to_points(1e6).len(), 5);
+    }
+
+    #[test]
+    fn arc_segment_to_points() {
+        let center = Coord::from_degrees(39.5, -119.77);
+        let arc = |angle_start, angle_end, direction| Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(center),
+                PolygonSegment::ArcSegment(ArcSegment {
+                    centerpoint: center,
+                    radius: 10.0,
+                    angle_start,
+                    angle_end,
+                    direction,
+                }),
+            ],
+        };
+
+        let cw = arc(270.0, 290.0, Direction::Cw).to_points(5.0);
+        assert_eq!(cw.first(), Some(&center));
+        assert_eq!(cw.last(), Some(&center));
+        assert_ring_within(&cw[1..cw.len() - 1], &center, 10.0 * 1852.0, 5.0);
+        let first = geodesy::inverse(&center, &cw[1]).initial_bearing;
+        let last = geodesy::inverse(&center, &cw[cw.len() - 2]).initial_bearing;
+        assert!((first - 270.0).abs() < 1e-3);
+        assert!((last - 290.0).abs() < 1e-3);
+
+        // The counterclockwise arc covers the other 340 degrees
+        let ccw = arc(270.0, 290.0, Direction::Ccw).to_points(5.0);
+        assert!(ccw.len() > cw.len() * 10);
+        let second = geodesy::inverse(&center, &ccw[2]).initial_bearing;
+        assert!(second < 270.0);
+    }
+
+    #[test]
+    fn arc_to_points() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let start = geodesy::direct(&center, 0.0, 10_000.0);
+        let end = geodesy::direct(&center, 90.0, 11_000.0);
+        let geom = Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(center),
+                PolygonSegment::Arc(Arc {
+                    centerpoint: center,
+                    start,
+                    end,
+                    direction: Direction::Cw,
+                }),
+            ],
+        };
+        let points = geom.to_points(1.0);
+
+        // Exact end points, closed ring
+        assert_eq!(points[..2], [center, start]);
+        assert_eq!(points[points.len() - 2..], [end, center]);
+
+        // Radius is interpolated between start and end
+        let mid = points[points.len() / 2];
+        let inv = geodesy::inverse(&center, &mid);
+        assert!((inv.initial_bearing - 45.0).abs() < 2.0);
+        assert!((inv.distance - 10_500.0).abs() < 30.0);
+    }
+
+    #[test]
+    fn polygon_to_points() {
+        let a = Coord::from_degrees(47.0, 8.0);
+        let b = Coord::from_degrees(47.0, 9.0);
+        let c = Coord::from_degrees(48.0, 9.0);
+        let open = Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(a),
+                PolygonSegment::Point(b),
+                PolygonSegment::Point(b),
+                PolygonSegment::Point(c),
+            ],
+        };
+        assert_eq!(open.to_points(10.0), [a, b, c, a]);
+
+        let closed = Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(a),
+                PolygonSegment::Point(b),
+                PolygonSegment::Point(c),
+                PolygonSegment::Point(a),
+            ],
+        };
+        assert_eq!(closed.to_points(10.0), [a, b, c, a]);
+
+        let empty = Geometry::Polygon { segments: vec![] };
+        assert!(empty.to_points(10.0).is_empty());
+    }
+
+    #[test]
+    fn circle_contains() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let geom = Geometry::Circle {
+            centerpoint: center,
+            radius: 5.0,
+        };
+        assert!(geom.contains(&center));
+        assert!(geom.contains(&geodesy::direct(&center, 33.0, 9259.0)));
+        assert!(!geom.contains(&geodesy::direct(&center, 33.0, 9261.0)));
+    }
+
+    #[test]
+    fn polygon_contains() {
+        // Square with a notch in the northern edge
+        let geom = Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(Coord::from_degrees(47.0, 8.0)),
+                PolygonSegment::Point(Coord::from_degrees(48.0, 8.0)),
+                PolygonSegment::Point(Coord::from_degrees(47.5, 8.5)),
+                PolygonSegment::Point(Coord::from_degrees(48.0, 9.0)),
+                PolygonSegment::Point(Coord::from_degrees(47.0, 9.0)),
+            ],
+        };
+        assert!(geom.contains(&Coord::from_degrees(47.2, 8.5)));
+        assert!(geom.contains(&Coord::from_degrees(47.9, 8.1)));
+        assert!(!geom.contains(&Coord::from_degrees(47.9, 8.5)));
+        assert!(!geom.contains(&Coord::from_degrees(46.9, 8.5)));
+
+        let empty = Geometry::Polygon { segments: vec![] };
+        assert!(!empty.contains(&Coord::from_degrees(47.0, 8.0)));
+    }
+
+    #[test]
+    fn arc_polygon_contains() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let radius = 10.0 * 1852.0;
+        // Convex bulge: half disk east of the meridian through the center
+        let half_disk = |direction| Geometry::Polygon {
+            segments: vec![PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 10.0,
+                angle_start: 0.0,
+                angle_end: 180.0,
+                direction,
+            })],
+        };
+        let east = half_disk(Direction::Cw);
+        let west = half_disk(Direction::Ccw);
+        for (bearing, distance) in [(10.0, radius - 5.0), (90.0, radius - 5.0), (170.0, 100.0)] {
+            let p = geodesy::direct(&center, bearing, distance);
+            assert!(east.contains(&p), "{bearing} {distance}");
+            assert!(!west.contains(&p), "{bearing} {distance}");
+        }
+        for (bearing, distance) in [(90.0, radius + 5.0), (350.0, radius - 5.0)] {
+            assert!(!east.contains(&geodesy::direct(&center, bearing, distance)));
+        }
+
+        // Points near the arc but outside of the chord polygon, for a small
+        // and a large arc
+        let pie = |angle_end, direction| Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(center),
+                PolygonSegment::ArcSegment(ArcSegment {
+                    centerpoint: center,
+                    radius: 10.0,
+                    angle_start: 0.0,
+                    angle_end,
+                    direction,
+                }),
+            ],
+        };
+        let small = pie(90.0, Direction::Cw);
+        let large = pie(90.0, Direction::Ccw);
+        let near_arc = geodesy::direct(&center, 45.0, radius - 5.0);
+        let beyond_arc = geodesy::direct(&center, 45.0, radius + 5.0);
+        let opposite = geodesy::direct(&center, 225.0, radius - 5.0);
+        assert!(small.contains(&near_arc));
+        assert!(!small.contains(&beyond_arc));
+        assert!(!small.contains(&opposite));
+        assert!(!large.contains(&near_arc));
+        assert!(large.contains(&opposite));
+        assert!(!large.contains(&geodesy::direct(&center, 225.0, radius + 5.0)));
+    }
+
+    #[test]
+    fn arc_polygon_contains_matches_points() {
+        // Rounded rectangle with DB arcs (one concave) compared against the
+        // densified ring, away from the boundary
+        let center = Coord::from_degrees(46.5, 7.5);
+        let geom = Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(Coord::from_degrees(46.3, 7.0)),
+                PolygonSegment::Point(Coord::from_degrees(46.7, 7.0)),
+                PolygonSegment::Arc(Arc {
+                    centerpoint: Coord::from_degrees(46.7, 7.25),
+                    start: Coord::from_degrees(46.7, 7.1),
+                    end: Coord::from_degrees(46.7, 7.4),
+                    direction: Direction::Cw,
+                }),
+                PolygonSegment::Point(Coord::from_degrees(46.7, 8.0)),
+                PolygonSegment::Point(Coord::from_degrees(46.3, 8.0)),
+                PolygonSegment::Arc(Arc {
+                    centerpoint: Coord::from_degrees(46.3, 7.5),
+                    start: Coord::from_degrees(46.3, 7.7),
+                    end: Coord::from_degrees(46.3, 7.3),
+                    direction: Direction::Ccw,
+                }),
+            ],
+        };
+        let ring = geom.to_points(0.1);
+        let mut inside = 0;
+        for i in 0..=40 {
+            for j in 0..=40 {
+                let p = Coord::from_degrees(
+                    center.lat() + (f64::from(i) - 20.0) * 0.03,
+                    center.lng() + (f64::from(j) - 20.0) * 0.06,
+                );
+                let expected = ring_contains(&ring, &p);
+                assert_eq!(geom.contains(&p), expected, "{p:?}");
+                inside += usize::from(expected);
+            }
+        }
+        assert!(inside > 100);
+    }
+
+    fn dense_bbox(geom: &Geometry) -> BoundingBox {
+        let points = geom.to_points(0.01);
+        let mut bbox = BoundingBox::from_coord(&points[0]);
+        for point in &points {
+            bbox.extend(point);
+        }
+        bbox
+    }
+
+    fn assert_bbox_close(bbox: &BoundingBox, expected: &BoundingBox) {
+        // Never smaller than the densified ring (up to the rounding of its
+        // points to milli-arc-seconds), and at most a few centimetres larger
+        let rounding = 0.5 / 3_600_000.0;
+        let eps = 1e-6;
+        assert!(
+            bbox.min_lat <= expected.min_lat + rounding,
+            "{bbox:?} {expected:?}"
+        );
+        assert!(
+            bbox.min_lng <= expected.min_lng + rounding,
+            "{bbox:?} {expected:?}"
+        );
+        assert!(
+            bbox.max_lat >= expected.max_lat - rounding,
+            "{bbox:?} {expected:?}"
+        );
+        assert!(
+            bbox.max_lng >= expected.max_lng - rounding,
+            "{bbox:?} {expected:?}"
+        );
+        assert!(
+            expected.min_lat - bbox.min_lat < eps,
+            "{bbox:?} {expected:?}"
+        );
+        assert!(
+            expected.min_lng - bbox.min_lng < eps,
+            "{bbox:?} {expected:?}"
+        );
+        assert!(
+            bbox.max_lat - expected.max_lat < eps,
+            "{bbox:?} {expected:?}"
+        );
+        assert!(
+            bbox.max_lng - expected.max_lng < eps,
+            "{bbox:?} {expected:?}"
+        );
+    }
+
+    #[test]
+    fn circle_bbox() {
+        for lat in [0.0, 47.0, -60.0, 80.0] {
+            let geom = Geometry::Circle {
+                centerpoint: Coord::from_degrees(lat, 8.0),
+                radius: 50.0,
+            };
+            assert_bbox_close(&geom.bbox().unwrap(), &dense_bbox(&geom));
+        }
+    }
+
+    #[test]
+    fn circle_bbox_enclosing_pole() {
+        let geom = Geometry::Circle {
+            centerpoint: Coord::from_degrees(89.5, 8.0),
+            radius: 60.0,
+        };
+        let bbox = geom.bbox().unwrap();
+        assert_eq!(
+            (bbox.min_lng, bbox.max_lat, bbox.max_lng),
+            (-180.0, 90.0, 180.0)
+        );
+        assert!((bbox.min_lat - 88.5).abs() < 0.01, "{bbox:?}");
+    }
+
+    #[test]
+    fn arc_bbox() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        // Arc bulging east beyond its end points
+        let geom = Geometry::Polygon {
+            segments: vec![PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 10.0,
+                angle_start: 10.0,
+                angle_end: 170.0,
+                direction: Direction::Cw,
+            })],
+        };
+        let bbox = geom.bbox().unwrap();
+        assert_bbox_close(&bbox, &dense_bbox(&geom));
+        let east = geodesy::direct_deg(47.0, 8.0, 90.0, 10.0 * 1852.0);
+        assert!(bbox.max_lng >= east.1);
+
+        // Same arc counterclockwise covers north, south and west
+        let geom = Geometry::Polygon {
+            segments: vec![PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 10.0,
+                angle_start: 10.0,
+                angle_end: 170.0,
+                direction: Direction::Ccw,
+            })],
+        };
+        assert_bbox_close(&geom.bbox().unwrap(), &dense_bbox(&geom));
+
+        // DB arc with differing radii, combined with points
+        let start = geodesy::direct(&center, 300.0, 9_000.0);
+        let geom = Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(Coord::from_degrees(46.9, 7.9)),
+                PolygonSegment::Arc(Arc {
+                    centerpoint: center,
+                    start,
+                    end: geodesy::direct(&center, 60.0, 9_500.0),
+                    direction: Direction::Cw,
+                }),
+            ],
+        };
+        let bbox = geom.bbox().unwrap();
+        let dense = dense_bbox(&geom);
+        assert!(bbox.max_lat >= dense.max_lat && bbox.max_lat - dense.max_lat < 0.01);
+        assert_eq!(bbox.min_lat, 46.9);
+        assert_eq!(bbox.min_lng, start.lng());
+
+        assert_eq!(Geometry::Polygon { segments: vec![] }.bbox(), None);
+    }
+
+    /// Area of a densified ring, using the same straight-edge integration.
+    fn dense_area(geom: &Geometry) -> f64 {
+        let points = geom.to_points(0.05);
+        points
+            .windows(2)
+            .map(|pair| geodesy::edge_area(&pair[0], &pair[1]))
+            .sum::<f64>()
+            .abs()
+    }
+
+    fn dense_perimeter(geom: &Geometry) -> f64 {
+        geom.to_points(0.05)
+            .windows(2)
+            .map(|pair| geodesy::inverse(&pair[0], &pair[1]).distance)
+            .sum()
+    }
+
+    fn assert_relative(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            ((actual - expected) / expected).abs() < tolerance,
+            "{actual} vs. {expected}"
+        );
+    }
+
+    #[test]
+    fn circle_area_and_perimeter() {
+        let geom = Geometry::Circle {
+            centerpoint: Coord::from_degrees(47.0, 8.0),
+            radius: 5.0,
+        };
+        let pi = std::f64::consts::PI;
+        assert_relative(geom.area_nm2(), pi * 25.0, 1e-4);
+        assert_relative(geom.perimeter_nm(), pi * 10.0, 1e-4);
+        assert_relative(geom.area_m2(), dense_area(&geom), 1e-5);
+        assert_relative(geom.perimeter_m(), dense_perimeter(&geom), 1e-5);
+    }
+
+    #[test]
+    fn polygon_area_and_perimeter() {
+        // 1° x 1° cell at the equator, counterclockwise
+        let geom = Geometry::Polygon {
+            segments: [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]
+                .into_iter()
+                .map(|(lat, lng)| PolygonSegment::Point(Coord::from_degrees(lat, lng)))
+                .collect(),
+        };
+        assert_relative(geom.area_m2(), 12_308.8e6, 1e-4);
+        assert_relative(geom.perimeter_m(), 2.0 * 111_319.5 + 2.0 * 110_574.4, 1e-4);
+
+        let empty = Geometry::Polygon { segments: vec![] };
+        assert_eq!((empty.area_m2(), empty.perimeter_m()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn arc_polygon_area_and_perimeter() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let arc = |angle_start, angle_end, direction| {
+            PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 8.0,
+                angle_start,
+                angle_end,
+                direction,
+            })
+        };
+        let geoms = [
+            // Pie slice
+            vec![
+                PolygonSegment::Point(center),
+                arc(30.0, 120.0, Direction::Cw),
+            ],
+            // Major arc
+            vec![
+                PolygonSegment::Point(center),
+                arc(30.0, 120.0, Direction::Ccw),
+            ],
+            // Bite taken out of a square
+            vec![
+                PolygonSegment::Point(Coord::from_degrees(47.2, 7.8)),
+                PolygonSegment::Point(Coord::from_degrees(47.2, 8.2)),
+                arc(80.0, 100.0, Direction::Ccw),
+                PolygonSegment::Point(Coord::from_degrees(46.8, 8.2)),
+                PolygonSegment::Point(Coord::from_degrees(46.8, 7.8)),
+            ],
+            // DB arc with differing radii
+            vec![
+                PolygonSegment::Point(center),
+                PolygonSegment::Arc(Arc {
+                    centerpoint: center,
+                    start: geodesy::direct(&center, 300.0, 9_000.0),
+                    end: geodesy::direct(&center, 60.0, 15_000.0),
+                    direction: Direction::Cw,
+                }),
+            ],
+            // Full circle
+            vec![arc(45.0, 45.0, Direction::Cw)],
+        ];
+        for segments in geoms {
+            let geom = Geometry::Polygon { segments };
+            assert_relative(geom.area_m2(), dense_area(&geom), 1e-4);
+            assert_relative(geom.perimeter_m(), dense_perimeter(&geom), 1e-4);
+        }
+    }
+
+    #[test]
+    fn arc_conversion() {
+        let center = Coord::from_degrees(39.5, -119.77);
+        let arc_segment = ArcSegment {
+            centerpoint: center,
+            radius: 10.0,
+            angle_start: 270.0,
+            angle_end: 290.0,
+            direction: Direction::Cw,
+        };
+        let arc = arc_segment.to_arc();
+        assert_eq!(arc.direction, Direction::Cw);
+        assert!(arc.radius_mismatch() < 0.05);
+        let start = geodesy::inverse(&center, &arc.start);
+        assert!((start.distance - 18_520.0).abs() < 0.05);
+        assert!((start.initial_bearing - 270.0).abs() < 1e-3);
+
+        let back = arc.to_arc_segment();
+        assert_eq!(back.centerpoint, center);
+        assert!((back.radius - 10.0).abs() < 1e-5);
+        assert!((back.angle_start - 270.0).abs() < 1e-3);
+        assert!((back.angle_end - 290.0).abs() < 1e-3);
+        assert_eq!(back.direction, Direction::Cw);
+
+        let inconsistent = Arc {
+            centerpoint: center,
+            start: geodesy::direct(&center, 10.0, 5_000.0),
+            end: geodesy::direct(&center, 80.0, 5_300.0),
+            direction: Direction::Ccw,
+        };
+        assert!((inconsistent.radius_mismatch() - 300.0).abs() < 0.05);
+        let arc_segment = inconsistent.to_arc_segment();
+        assert!((f64::from(arc_segment.radius) * 1852.0 - 5_150.0).abs() < 0.05);
+        assert_eq!(arc_segment.direction, Direction::Ccw);
+    }
+
+    #[test]
+    fn antimeridian_polygon() {
+        let polygon = |lng: f64| Geometry::Polygon {
+            segments: [(10.0, -10.0), (10.0, 10.0), (-10.0, 10.0), (-10.0, -10.0)]
+                .into_iter()
+                .map(|(lat, d_lng)| {
+                    let lng = geodesy::normalize_longitude(lng + d_lng);
+                    PolygonSegment::Point(Coord::from_degrees(lat, lng))
+                })
+                .collect(),
+        };
+        let geom = polygon(180.0);
+
+        let bbox = geom.bbox().unwrap();
+        assert!(bbox.crosses_antimeridian());
+        assert_eq!((bbox.min_lng, bbox.max_lng), (170.0, -170.0));
+
+        assert!(geom.contains(&Coord::from_degrees(0.0, 180.0)));
+        assert!(geom.contains(&Coord::from_degrees(5.0, -175.0)));
+        assert!(geom.contains(&Coord::from_degrees(-5.0, 175.0)));
+        assert!(!geom.contains(&Coord::from_degrees(0.0, 0.0)));
+        assert!(!geom.contains(&Coord::from_degrees(0.0, 165.0)));
+
+        assert_relative(geom.area_m2(), polygon(0.0).area_m2(), 1e-9);
+        assert_eq!(geom.orientation(), polygon(0.0).orientation());
+
+        let rings = geom.to_rings(100.0);
+        assert_eq!(rings.len(), 2);
+        for ring in &rings {
+            assert_eq!(ring.first(), ring.last());
+            let mut lngs = ring.iter().map(Coord::lng);
+            assert!(lngs.clone().all(|lng| lng >= 170.0) || lngs.all(|lng| lng <= -170.0));
+        }
+        assert_eq!(
+            polygon(0.0).to_rings(100.0),
+            vec![polygon(0.0).to_points(100.0)]
+        );
+    }
+
+    #[test]
+    fn antimeridian_circle() {
+        let geom = Geometry::Circle {
+            centerpoint: Coord::from_degrees(-17.0, 179.9),
+            radius: 20.0,
+        };
+        let bbox = geom.bbox().unwrap();
+        assert!(bbox.crosses_antimeridian());
+        assert!(bbox.min_lng > 179.0 && bbox.max_lng < -179.0, "{bbox:?}");
+        assert_bbox_close(&bbox, &dense_bbox(&geom));
+
+        assert!(geom.contains(&Coord::from_degrees(-17.0, -179.9)));
+        let rings = geom.to_rings(10.0);
+        assert_eq!(rings.len(), 2);
+    }
+
+    #[test]
+    fn polar_polygon() {
+        let geom = Geometry::Polygon {
+            segments: [0.0, 90.0, 180.0, -90.0]
+                .into_iter()
+                .map(|lng| PolygonSegment::Point(Coord::from_degrees(80.0, lng)))
+                .collect(),
+        };
+        assert!(geom.contains(&Coord::from_degrees(89.0, 45.0)));
+        assert!(geom.contains(&Coord::from_degrees(85.0, -135.0)));
+        assert!(!geom.contains(&Coord::from_degrees(70.0, 0.0)));
+
+        let bbox = geom.bbox().unwrap();
+        assert_eq!(
+            (bbox.min_lat, bbox.max_lat, bbox.min_lng, bbox.max_lng),
+            (80.0, 90.0, -180.0, 180.0)
+        );
+
+        // Polar cap north of 80°
+        let cap =
+            2.0 * std::f64::consts::PI * (geodesy::zone_area(90.0) - geodesy::zone_area(80.0));
+        assert_relative(geom.area_m2(), cap, 1e-9);
+        assert_eq!(geom.orientation(), Some(Orientation::Counterclockwise));
+
+        let rings = geom.to_rings(100.0);
+        assert_eq!(rings.len(), 2);
+        for ring in &rings {
+            assert!(ring.iter().any(|point| point.lat() == 90.0));
+        }
+
+        // Same ring around the south pole
+        let geom = Geometry::Polygon {
+            segments: [0.0, 90.0, 180.0, -90.0]
+                .into_iter()
+                .map(|lng| PolygonSegment::Point(Coord::from_degrees(-80.0, lng)))
+                .collect(),
+        };
+        assert!(geom.contains(&Coord::from_degrees(-89.0, 45.0)));
+        assert!(!geom.contains(&Coord::from_degrees(-70.0, 0.0)));
+        assert_relative(geom.area_m2(), cap, 1e-9);
+        assert_eq!(geom.bbox().unwrap().min_lat, -90.0);
+    }
+
+    #[test]
+    fn polar_circle_rings() {
+        let geom = Geometry::Circle {
+            centerpoint: Coord::from_degrees(89.5, 8.0),
+            radius: 60.0,
+        };
+        let rings = geom.to_rings(10.0);
+        assert_eq!(rings.len(), 2);
+        let area: f64 = rings
+            .iter()
+            .map(|ring| {
+                ring.windows(2)
+                    .map(|pair| geodesy::edge_area(&pair[0], &pair[1]))
+                    .sum::<f64>()
+            })
+            .sum();
+        assert_relative(area.abs(), geom.area_m2(), 1e-4);
+    }
+}
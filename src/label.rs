@@ -0,0 +1,258 @@
+use std::{cmp::Ordering, collections::BinaryHeap, f64::consts::SQRT_2};
+
+use crate::{Coord, Geometry, geodesy, geometry};
+
+/// Tolerance in metres for flattening arcs before searching the label point.
+const ARC_TOLERANCE: f64 = 10.0;
+
+/// Precision of the label point, relative to the size of the geometry.
+const PRECISION: f64 = 1e-3;
+
+impl Geometry {
+    /// Returns a point well inside the geometry to place a label at, or
+    /// `None` for polygons without area.
+    ///
+    /// This is the pole of inaccessibility: the point inside the flattened
+    /// geometry (see [`Self::to_points`]) that is farthest from its boundary,
+    /// found with the "polylabel" algorithm. Unlike the centroid, it always
+    /// lies inside concave or C-shaped polygons. Distances are measured in an
+    /// equirectangular projection around the geometry, circles return their
+    /// centerpoint.
+    ///
+    /// ```
+    /// use openair::{Coord, Geometry, PolygonSegment};
+    ///
+    /// let point = |lat, lng| PolygonSegment::Point(Coord::from_degrees(lat, lng));
+    /// let geom = Geometry::Polygon {
+    ///     segments: vec![point(47.0, 8.0), point(47.0, 9.0), point(46.0, 9.0), point(46.0, 8.0)],
+    /// };
+    /// let label = geom.label_point().unwrap();
+    /// assert!((label.lat() - 46.5).abs() < 0.01);
+    /// assert!((label.lng() - 8.5).abs() < 0.01);
+    /// ```
+    pub fn label_point(&self) -> Option<Coord> {
+        let ring = match self {
+            Self::Circle { centerpoint, .. } => return Some(*centerpoint),
+            Self::Polygon { .. } => self.to_points(ARC_TOLERANCE),
+        };
+        let planar = geometry::planar_ring(&ring);
+        if planar.len() < 3 {
+            return None;
+        }
+
+        let (min_y, max_y) = bounds(planar.iter().map(|&(_, y)| y));
+        let scale = ((min_y + max_y) / 2.0).to_radians().cos().max(0.01);
+        let polygon: Vec<(f64, f64)> = planar.iter().map(|&(x, y)| (x * scale, y)).collect();
+        let (x, y) = polylabel(&polygon)?;
+        Some(Coord::from_degrees(
+            y,
+            geodesy::normalize_longitude(x / scale),
+        ))
+    }
+}
+
+/// A square cell of the search grid.
+struct Cell {
+    x: f64,
+    y: f64,
+    /// Half the size of the cell
+    h: f64,
+    /// Signed distance from the center of the cell to the polygon boundary
+    d: f64,
+    /// Maximum distance to the boundary of any point within the cell
+    max: f64,
+}
+
+impl Cell {
+    fn new(x: f64, y: f64, h: f64, polygon: &[(f64, f64)]) -> Self {
+        let d = signed_distance(polygon, x, y);
+        Self {
+            x,
+            y,
+            h,
+            d,
+            max: d + h * SQRT_2,
+        }
+    }
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cell {}
+
+impl PartialOrd for Cell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.max.total_cmp(&other.max)
+    }
+}
+
+/// Pole of inaccessibility of an implicitly closed planar ring, or `None` if
+/// the ring has no interior.
+fn polylabel(polygon: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let (min_x, max_x) = bounds(polygon.iter().map(|&(x, _)| x));
+    let (min_y, max_y) = bounds(polygon.iter().map(|&(_, y)| y));
+    let (width, height) = (max_x - min_x, max_y - min_y);
+    let cell_size = width.min(height);
+    if cell_size.is_nan() || cell_size <= 0.0 {
+        return None;
+    }
+    let precision = width.max(height) * PRECISION;
+
+    // Cover the polygon with initial cells
+    let h = cell_size / 2.0;
+    let mut queue = BinaryHeap::new();
+    let mut x = min_x;
+    while x < max_x {
+        let mut y = min_y;
+        while y < max_y {
+            queue.push(Cell::new(x + h, y + h, h, polygon));
+            y += cell_size;
+        }
+        x += cell_size;
+    }
+
+    // Start with the centroid, or the center of the bounding box
+    let mut best = centroid_cell(polygon);
+    let center = Cell::new(min_x + width / 2.0, min_y + height / 2.0, 0.0, polygon);
+    if center.d > best.d {
+        best = center;
+    }
+
+    while let Some(cell) = queue.pop() {
+        if cell.d > best.d {
+            best = Cell::new(cell.x, cell.y, 0.0, polygon);
+        }
+        // Skip cells that cannot contain a significantly better point
+        if cell.max - best.d <= precision {
+            continue;
+        }
+        let h = cell.h / 2.0;
+        for (dx, dy) in [(-h, -h), (h, -h), (-h, h), (h, h)] {
+            queue.push(Cell::new(cell.x + dx, cell.y + dy, h, polygon));
+        }
+    }
+
+    (best.d > 0.0).then_some((best.x, best.y))
+}
+
+/// Cell at the area-weighted centroid of the polygon, or at its first point
+/// if the polygon has no area.
+fn centroid_cell(polygon: &[(f64, f64)]) -> Cell {
+    let (mut x, mut y, mut area) = (0.0, 0.0, 0.0);
+    for (i, &(ax, ay)) in polygon.iter().enumerate() {
+        let (bx, by) = polygon[(i + 1) % polygon.len()];
+        let f = ax * by - bx * ay;
+        x += (ax + bx) * f;
+        y += (ay + by) * f;
+        area += f * 3.0;
+    }
+    if area == 0.0 {
+        let (x, y) = polygon[0];
+        return Cell::new(x, y, 0.0, polygon);
+    }
+    Cell::new(x / area, y / area, 0.0, polygon)
+}
+
+/// Distance from the point to the boundary of the polygon, negative if the
+/// point lies outside.
+fn signed_distance(polygon: &[(f64, f64)], x: f64, y: f64) -> f64 {
+    let mut min = f64::INFINITY;
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        min = min.min(geometry::segment_distance((x, y), a, b));
+    }
+    if geometry::planar_contains(polygon, x, y) {
+        min
+    } else {
+        -min
+    }
+}
+
+/// Minimum and maximum of the values.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArcSegment, Direction, PolygonSegment};
+
+    fn point(lat: f64, lng: f64) -> PolygonSegment {
+        PolygonSegment::Point(Coord::from_degrees(lat, lng))
+    }
+
+    fn polygon(segments: Vec<PolygonSegment>) -> Geometry {
+        Geometry::Polygon { segments }
+    }
+
+    #[test]
+    fn c_shape() {
+        // The centroid of this shape lies in the gap at (46.5, 8.5)
+        let geom = polygon(vec![
+            point(47.0, 8.0),
+            point(47.0, 9.0),
+            point(46.8, 9.0),
+            point(46.8, 8.2),
+            point(46.2, 8.2),
+            point(46.2, 9.0),
+            point(46.0, 9.0),
+            point(46.0, 8.0),
+        ]);
+        let label = geom.label_point().unwrap();
+        assert!(geom.contains(&label), "{label:?}");
+    }
+
+    #[test]
+    fn circle_and_arcs() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let circle = Geometry::Circle {
+            centerpoint: center,
+            radius: 5.0,
+        };
+        assert_eq!(circle.label_point(), Some(center));
+
+        // A full circle as a polygon
+        let geom = polygon(vec![PolygonSegment::ArcSegment(ArcSegment {
+            centerpoint: center,
+            radius: 5.0,
+            angle_start: 0.0,
+            angle_end: 0.0,
+            direction: Direction::Cw,
+        })]);
+        let label = geom.label_point().unwrap();
+        assert!(label.distance_to(&center) < 50.0, "{label:?}");
+    }
+
+    #[test]
+    fn antimeridian() {
+        let geom = polygon(vec![
+            point(10.0, 170.0),
+            point(10.0, -170.0),
+            point(-10.0, -170.0),
+            point(-10.0, 170.0),
+        ]);
+        let label = geom.label_point().unwrap();
+        assert!(label.lat().abs() < 0.1, "{label:?}");
+        assert!((label.lng().abs() - 180.0).abs() < 0.1, "{label:?}");
+    }
+
+    #[test]
+    fn degenerate() {
+        assert_eq!(polygon(vec![]).label_point(), None);
+        let line = polygon(vec![point(47.0, 8.0), point(47.0, 9.0)]);
+        assert_eq!(line.label_point(), None);
+    }
+}
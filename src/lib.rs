@@ -32,19 +32,26 @@
 //!     lower_bound: Altitude::Gnd,
 //!     upper_bound: Altitude::FlightLevel(100),
 //!     geom: Geometry::Circle {
-//!         centerpoint: Coord { lat: 47.0, lng: 8.0 },
+//!         centerpoint: Coord::from_degrees(47.0, 8.0),
 //!         radius: 5.0,
 //!     },
 //!     frequency: None,
 //!     call_sign: None,
 //!     transponder_code: None,
 //!     activation_times: None,
+//!     label_points: vec![],
+//!     extensions: vec![],
 //! };
 //!
 //! let file = File::create("output.txt").unwrap();
 //! openair::write(file, [&airspace]).unwrap();
 //! ```
 //!
+//! The output style (line endings, coordinate notation, altitude spelling,
+//! airspace separator) can be customized with [`WriterOptions`] and
+//! [`write_with`]. To write a file header, comments, or airspaces one at a
+//! time, use an [`AirspaceWriter`].
+//!
 //! ## Implementation Notes
 //!
 //! Unfortunately the `OpenAir` format is really underspecified. Every device
@@ -56,16 +63,29 @@
 //! This parser tries to be very lenient when parsing, based on real life data.
 //! The end of an airspace is reached when the next one starts (with an `AC`
 //! record) or when the file ends.
-//!
-//! Note: AT records (label placement hints) are currently ignored
 #![deny(clippy::all)]
 
 mod activations;
+mod airspace_type;
 mod altitude;
+mod bbox;
+mod boolean;
 mod classes;
+mod conflicts;
+mod construct;
+mod containment;
 mod coords;
+mod fit;
+mod geodesy;
 mod geometry;
+mod label;
+mod normalize;
+mod radio;
 mod record;
+mod remap;
+mod simplify;
+mod validate;
+mod writer;
 
 use std::{
     fmt,
@@ -76,14 +96,27 @@ use log::debug;
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
-use crate::record::Record;
 pub use crate::{
     activations::ActivationTimes,
+    airspace_type::AirspaceType,
     altitude::Altitude,
+    bbox::BoundingBox,
     classes::Class,
-    coords::Coord,
+    conflicts::{ConflictReport, Duplicate, Overlap, VerticalOverlap, find_conflicts},
+    containment::{Containment, Limit, PositionContext},
+    coords::{Coord, CoordFormat},
     geometry::{Arc, ArcSegment, Direction, Geometry, PolygonSegment},
+    normalize::{Closing, NormalizeOptions, NormalizeReport, Orientation},
+    radio::{ChannelSpacing, Frequency, Squawk},
+    remap::{RemapMatch, RemapRule, RemapTable},
+    simplify::{SimplifyMode, simplify_to_budget},
+    validate::GeometryIssue,
+    writer::{
+        AirspaceWriter, ArcSpacing, ArcStyle, CoordStyle, GroundSpelling, LineEnding, MslSpelling,
+        Separator, TypeSpelling, UnlimitedSpelling, WriterOptions,
+    },
 };
+use crate::{record::Record, writer::Variables};
 
 /// An airspace.
 #[derive(Debug, PartialEq)]
@@ -97,7 +130,7 @@ pub struct Airspace {
     /// The airspace type (extension record)
     #[cfg_attr(feature = "serde", serde(rename = "type"))]
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub type_: Option<String>,
+    pub type_: Option<AirspaceType>,
     /// The lower bound of the airspace
     pub lower_bound: Altitude,
     /// The upper bound of the airspace
@@ -107,16 +140,22 @@ pub struct Airspace {
     /// Frequency of the controlling ATC-station or other authority in that
     /// particular airspace (extension record)
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub frequency: Option<String>,
+    pub frequency: Option<Frequency>,
     /// Call-sign for this station
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub call_sign: Option<String>,
     /// Transponder code associated with this airspace
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub transponder_code: Option<u16>,
+    pub transponder_code: Option<Squawk>,
     /// Airspace activation times
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub activation_times: Option<ActivationTimes>,
+    /// Label placement hints (`AT` records), in file order
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub label_points: Vec<Coord>,
+    /// Unknown `A*` extension records as (tag, data) pairs, in file order
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub extensions: Vec<(String, String)>,
 }
 
 impl fmt::Display for Airspace {
@@ -130,79 +169,166 @@ impl fmt::Display for Airspace {
 }
 
 impl Airspace {
+    /// Returns the bounding box of the airspace (see [`Geometry::bbox`]).
+    pub fn bbox(&self) -> Option<BoundingBox> {
+        self.geom.bbox()
+    }
+
+    /// Returns the area of the airspace in m² (see [`Geometry::area_m2`]).
+    pub fn area_m2(&self) -> f64 {
+        self.geom.area_m2()
+    }
+
+    /// Returns the area of the airspace in NM² (see [`Geometry::area_m2`]).
+    pub fn area_nm2(&self) -> f64 {
+        self.geom.area_nm2()
+    }
+
+    /// Returns the length of the lateral boundary in metres (see
+    /// [`Geometry::perimeter_m`]).
+    pub fn perimeter_m(&self) -> f64 {
+        self.geom.perimeter_m()
+    }
+
+    /// Returns the length of the lateral boundary in NM (see
+    /// [`Geometry::perimeter_m`]).
+    pub fn perimeter_nm(&self) -> f64 {
+        self.geom.perimeter_nm()
+    }
+
+    /// Returns true if the coordinate lies inside the lateral limits of the
+    /// airspace, ignoring its vertical limits (see [`Geometry::contains`]).
+    pub fn contains_horizontally(&self, coord: &Coord) -> bool {
+        self.geom.contains(coord)
+    }
+
+    /// Locate a position (altitude in feet AMSL) relative to the airspace.
+    ///
+    /// The lateral limits are checked first, followed by the activation times
+    /// (if the context contains a time) and the vertical limits. Fails if a
+    /// vertical limit cannot be resolved, e.g. an AGL limit without known
+    /// ground elevation.
+    pub fn contains_position(
+        &self,
+        coord: &Coord,
+        altitude: f64,
+        context: &PositionContext,
+    ) -> Result<Containment, String> {
+        if !self.contains_horizontally(coord) {
+            return Ok(Containment::OutsideHorizontally);
+        }
+        if let (Some(time), Some(activation_times)) = (&context.time, &self.activation_times)
+            && !activation_times.is_active_at(time)
+        {
+            return Ok(Containment::Inactive);
+        }
+        let lower = containment::resolve_altitude(&self.lower_bound, context)?;
+        let upper = containment::resolve_altitude(&self.upper_bound, context)?;
+        Ok(containment::vertical_containment(altitude, lower, upper))
+    }
+
     /// Writes the airspace in OpenAir format.
-    pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+    pub fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        self.write_with(writer, &WriterOptions::default())
+    }
+
+    /// Writes the airspace in OpenAir format using the given options.
+    pub fn write_with<W: Write>(
+        &self,
+        mut writer: W,
+        options: &WriterOptions,
+    ) -> std::io::Result<()> {
+        let (class, type_) = match options.remap {
+            Some(ref table) => table.remapped(self),
+            None => (self.class, self.type_.clone()),
+        };
+
         // 1. AC (class) - required
-        Record::AirspaceClass(self.class).write(&mut writer)?;
+        Record::AirspaceClass(class).write(&mut writer, options)?;
 
         // 2. AY (type) - optional
-        if let Some(ref type_) = self.type_ {
-            Record::AirspaceType(type_).write(&mut writer)?;
+        if let Some(type_) = type_ {
+            Record::AirspaceType(type_).write(&mut writer, options)?;
         }
 
         // 3. AN (name) - required
-        Record::AirspaceName(&self.name).write(&mut writer)?;
+        Record::AirspaceName(&self.name).write(&mut writer, options)?;
 
         // 4. AL (lower bound) - required
-        Record::LowerBound(self.lower_bound.clone()).write(&mut writer)?;
+        Record::LowerBound(self.lower_bound.clone()).write(&mut writer, options)?;
 
         // 5. AH (upper bound) - required
-        Record::UpperBound(self.upper_bound.clone()).write(&mut writer)?;
+        Record::UpperBound(self.upper_bound.clone()).write(&mut writer, options)?;
 
         // 6. AF (frequency) - optional
         if let Some(ref frequency) = self.frequency {
-            Record::Frequency(frequency).write(&mut writer)?;
+            Record::Frequency(frequency.clone()).write(&mut writer, options)?;
         }
 
         // 7. AG (call sign) - optional
         if let Some(ref call_sign) = self.call_sign {
-            Record::CallSign(call_sign).write(&mut writer)?;
+            Record::CallSign(call_sign).write(&mut writer, options)?;
         }
 
         // 8. AX (transponder code) - optional
-        if let Some(transponder_code) = self.transponder_code {
-            Record::TransponderCode(transponder_code).write(&mut writer)?;
+        if let Some(ref transponder_code) = self.transponder_code {
+            Record::TransponderCode(transponder_code.clone()).write(&mut writer, options)?;
         }
 
         // 9. AA (activation times) - optional
         if let Some(activation_times) = self.activation_times {
-            Record::ActivationTimes(activation_times).write(&mut writer)?;
+            Record::ActivationTimes(activation_times).write(&mut writer, options)?;
+        }
+
+        // 10. Unknown extension records - optional
+        for (tag, data) in &self.extensions {
+            Record::UnknownExtension { tag, data }.write(&mut writer, options)?;
         }
 
-        // 10. Geometry
+        // 11. AT (label placement hints) - optional
+        if !self.label_points.is_empty() {
+            for coord in &self.label_points {
+                Record::LabelPlacement(*coord).write(&mut writer, options)?;
+            }
+        } else if options.compute_label_points
+            && let Some(coord) = self.geom.label_point()
+        {
+            Record::LabelPlacement(coord).write(&mut writer, options)?;
+        }
+
+        // 12. Geometry
+        let mut vars = Variables::default();
         match &self.geom {
             Geometry::Circle {
                 centerpoint,
                 radius,
             } => {
-                Record::VarX(centerpoint.clone()).write(&mut writer)?;
-                Record::CircleRadius(*radius).write(&mut writer)?;
+                vars.write_x(&mut writer, *centerpoint, options)?;
+                Record::CircleRadius(*radius).write(&mut writer, options)?;
             }
             Geometry::Polygon { segments } => {
                 for segment in segments {
                     match segment {
                         PolygonSegment::Point(coord) => {
-                            Record::Point(coord.clone()).write(&mut writer)?;
+                            Record::Point(*coord).write(&mut writer, options)?;
                         }
-                        PolygonSegment::ArcSegment(arc_segment) => {
-                            Record::VarX(arc_segment.centerpoint.clone()).write(&mut writer)?;
-                            Record::VarD(arc_segment.direction).write(&mut writer)?;
-                            Record::ArcSegmentData {
-                                radius: arc_segment.radius,
-                                angle_start: arc_segment.angle_start,
-                                angle_end: arc_segment.angle_end,
+                        PolygonSegment::ArcSegment(arc_segment) => match options.arc_style {
+                            ArcStyle::Db => {
+                                write_arc(&mut writer, &mut vars, &arc_segment.to_arc(), options)?;
                             }
-                            .write(&mut writer)?;
-                        }
-                        PolygonSegment::Arc(arc) => {
-                            Record::VarX(arc.centerpoint.clone()).write(&mut writer)?;
-                            Record::VarD(arc.direction).write(&mut writer)?;
-                            Record::ArcData {
-                                start: arc.start.clone(),
-                                end: arc.end.clone(),
+                            ArcStyle::Preserve | ArcStyle::Da => {
+                                write_arc_segment(&mut writer, &mut vars, arc_segment, options)?;
                             }
-                            .write(&mut writer)?;
-                        }
+                        },
+                        PolygonSegment::Arc(arc) => match options.arc_style {
+                            ArcStyle::Da => {
+                                let arc_segment = arc.to_arc_segment().rounded();
+                                write_arc_segment(&mut writer, &mut vars, &arc_segment, options)?;
+                            }
+                            ArcStyle::Preserve | ArcStyle::Db => {
+                                write_arc(&mut writer, &mut vars, arc, options)?;
+                            }
+                        },
                     }
                 }
             }
@@ -212,6 +338,37 @@ impl Airspace {
     }
 }
 
+fn write_arc_segment<W: Write>(
+    mut writer: W,
+    vars: &mut Variables,
+    arc_segment: &ArcSegment,
+    options: &WriterOptions,
+) -> std::io::Result<()> {
+    vars.write_x(&mut writer, arc_segment.centerpoint, options)?;
+    vars.write_d(&mut writer, arc_segment.direction, options)?;
+    Record::ArcSegmentData {
+        radius: arc_segment.radius,
+        angle_start: arc_segment.angle_start,
+        angle_end: arc_segment.angle_end,
+    }
+    .write(writer, options)
+}
+
+fn write_arc<W: Write>(
+    mut writer: W,
+    vars: &mut Variables,
+    arc: &Arc,
+    options: &WriterOptions,
+) -> std::io::Result<()> {
+    vars.write_x(&mut writer, arc.centerpoint, options)?;
+    vars.write_d(&mut writer, arc.direction, options)?;
+    Record::ArcData {
+        start: arc.start,
+        end: arc.end,
+    }
+    .write(writer, options)
+}
+
 struct OpenAirIterator<R: BufRead> {
     reader: R,
     line: Vec<u8>,
@@ -247,11 +404,13 @@ impl<R: BufRead> OpenAirIterator<R> {
         let mut lower_bound: Option<Altitude> = None;
         let mut upper_bound: Option<Altitude> = None;
         let mut geom: Option<Geometry> = None;
-        let mut type_: Option<String> = None;
-        let mut frequency: Option<String> = None;
+        let mut type_: Option<AirspaceType> = None;
+        let mut frequency: Option<Frequency> = None;
         let mut call_sign: Option<String> = None;
-        let mut transponder_code: Option<u16> = None;
+        let mut transponder_code: Option<Squawk> = None;
         let mut activation_times: Option<ActivationTimes> = None;
+        let mut label_points: Vec<Coord> = Vec::new();
+        let mut extensions: Vec<(String, String)> = Vec::new();
         let mut var_x: Option<Coord> = None;
         let mut var_d: Option<Direction> = None;
 
@@ -296,6 +455,8 @@ impl<R: BufRead> OpenAirIterator<R> {
                         call_sign,
                         transponder_code,
                         activation_times,
+                        label_points,
+                        extensions,
                     }));
                 }
                 return Ok(None);
@@ -336,11 +497,21 @@ impl<R: BufRead> OpenAirIterator<R> {
                     call_sign,
                     transponder_code,
                     activation_times,
+                    label_points,
+                    extensions,
                 }));
             }
 
-            // Update state tracking for header/non-header transitions.
-            let is_ignored = matches!(record, Record::Empty | Record::Comment);
+            // Update state tracking for header/non-header transitions. Label
+            // placement hints may appear between header records as well as
+            // between geometry records.
+            let is_ignored = matches!(
+                record,
+                Record::Empty
+                    | Record::Comment
+                    | Record::LabelPlacement(_)
+                    | Record::InvalidLabelPlacement
+            );
             if !is_ignored {
                 self.last_was_header = is_header;
             }
@@ -349,10 +520,13 @@ impl<R: BufRead> OpenAirIterator<R> {
             match record {
                 Record::Empty => {}
                 Record::Comment => {}
-                Record::LabelPlacement => {}
+                Record::LabelPlacement(coord) => label_points.push(coord),
+                Record::InvalidLabelPlacement => {}
                 Record::Pen => {}
                 Record::Brush => {}
-                Record::UnknownExtension(_) => {}
+                Record::UnknownExtension { tag, data } => {
+                    extensions.push((tag.to_string(), data.to_string()));
+                }
                 Record::AirspaceClass(parsed_class) => {
                     if class.is_some() {
                         return Err("Could not set class (already defined)".to_string());
@@ -381,13 +555,13 @@ impl<R: BufRead> OpenAirIterator<R> {
                     if type_.is_some() {
                         return Err("Could not set type (already defined)".to_string());
                     }
-                    type_ = Some(parsed_type.to_string());
+                    type_ = Some(parsed_type);
                 }
                 Record::Frequency(parsed_freq) => {
                     if frequency.is_some() {
                         return Err("Could not set frequency (already defined)".to_string());
                     }
-                    frequency = Some(parsed_freq.to_string());
+                    frequency = Some(parsed_freq);
                 }
                 Record::CallSign(parsed_call_sign) => {
                     if call_sign.is_some() {
@@ -432,7 +606,7 @@ impl<R: BufRead> OpenAirIterator<R> {
                 Record::CircleRadius(radius) => match (&geom, &var_x) {
                     (None, Some(centerpoint)) => {
                         geom = Some(Geometry::Circle {
-                            centerpoint: centerpoint.clone(),
+                            centerpoint: *centerpoint,
                             radius,
                         });
                     }
@@ -444,7 +618,7 @@ impl<R: BufRead> OpenAirIterator<R> {
                     angle_start,
                     angle_end,
                 } => {
-                    let centerpoint = var_x.clone().ok_or("Centerpoint missing".to_string())?;
+                    let centerpoint = var_x.ok_or("Centerpoint missing".to_string())?;
                     let direction = var_d.unwrap_or_default();
                     let arc_segment = ArcSegment {
                         centerpoint,
@@ -469,7 +643,7 @@ impl<R: BufRead> OpenAirIterator<R> {
                     }
                 }
                 Record::ArcData { start, end } => {
-                    let centerpoint = var_x.clone().ok_or("Centerpoint missing".to_string())?;
+                    let centerpoint = var_x.ok_or("Centerpoint missing".to_string())?;
                     let direction = var_d.unwrap_or_default();
                     let arc = Arc {
                         centerpoint,
@@ -514,16 +688,26 @@ pub fn parse<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Airspace, Str
 ///
 /// Airspaces are separated by blank lines.
 pub fn write<'a, W: Write, I: IntoIterator<Item = &'a Airspace>>(
-    mut writer: W,
+    writer: W,
     airspaces: I,
 ) -> std::io::Result<()> {
-    for (i, airspace) in airspaces.into_iter().enumerate() {
-        if i != 0 {
-            // Write blank line between airspaces
-            write!(writer, "\r\n")?;
-        }
-        airspace.write(&mut writer)?;
+    write_with(writer, airspaces, &WriterOptions::default())
+}
+
+/// Writes multiple airspaces in OpenAir format using the given options.
+///
+/// See [`AirspaceWriter`] for writing headers, comments or airspaces one at
+/// a time.
+pub fn write_with<'a, W: Write, I: IntoIterator<Item = &'a Airspace>>(
+    writer: W,
+    airspaces: I,
+    options: &WriterOptions,
+) -> std::io::Result<()> {
+    let mut writer = AirspaceWriter::with_options(writer, options.clone());
+    for airspace in airspaces {
+        writer.write_airspace(airspace)?;
     }
+    writer.finish()?;
     Ok(())
 }
 
@@ -546,16 +730,15 @@ mod tests {
             lower_bound: Altitude::Gnd,
             upper_bound: Altitude::FlightLevel(100),
             geom: Geometry::Circle {
-                centerpoint: Coord {
-                    lat: 47.0,
-                    lng: 8.0,
-                },
+                centerpoint: Coord::from_degrees(47.0, 8.0),
                 radius: 5.0,
             },
             frequency: None,
             call_sign: None,
             transponder_code: None,
             activation_times: None,
+            label_points: vec![],
+            extensions: vec![],
         };
 
         insta::assert_snapshot!(write_airspace(&airspace), @r"
@@ -573,20 +756,19 @@ mod tests {
         let airspace = Airspace {
             name: "Full Test Zone".to_string(),
             class: Class::Ctr,
-            type_: Some("CTR".to_string()),
+            type_: Some(AirspaceType::Ctr),
             lower_bound: Altitude::FeetAmsl(1000),
             upper_bound: Altitude::FeetAmsl(5000),
             geom: Geometry::Circle {
-                centerpoint: Coord {
-                    lat: 46.5,
-                    lng: 9.5,
-                },
+                centerpoint: Coord::from_degrees(46.5, 9.5),
                 radius: 10.0,
             },
-            frequency: Some("123.45".to_string()),
+            frequency: Some(Frequency::parse("123.45").unwrap()),
             call_sign: Some("TOWER".to_string()),
-            transponder_code: Some(7000),
+            transponder_code: Some(Squawk::new(7000).unwrap()),
             activation_times: Some("2023-12-16T12:00Z/2023-12-16T13:00Z".parse().unwrap()),
+            label_points: vec![],
+            extensions: vec![],
         };
 
         insta::assert_snapshot!(write_airspace(&airspace), @r"
@@ -614,24 +796,17 @@ mod tests {
             upper_bound: Altitude::Unlimited,
             geom: Geometry::Polygon {
                 segments: vec![
-                    PolygonSegment::Point(Coord {
-                        lat: 47.0,
-                        lng: 8.0,
-                    }),
-                    PolygonSegment::Point(Coord {
-                        lat: 47.0,
-                        lng: 9.0,
-                    }),
-                    PolygonSegment::Point(Coord {
-                        lat: 46.0,
-                        lng: 9.0,
-                    }),
+                    PolygonSegment::Point(Coord::from_degrees(47.0, 8.0)),
+                    PolygonSegment::Point(Coord::from_degrees(47.0, 9.0)),
+                    PolygonSegment::Point(Coord::from_degrees(46.0, 9.0)),
                 ],
             },
             frequency: None,
             call_sign: None,
             transponder_code: None,
             activation_times: None,
+            label_points: vec![],
+            extensions: vec![],
         };
 
         insta::assert_snapshot!(write_airspace(&airspace), @r"
@@ -655,15 +830,9 @@ mod tests {
             upper_bound: Altitude::FeetAmsl(3000),
             geom: Geometry::Polygon {
                 segments: vec![
-                    PolygonSegment::Point(Coord {
-                        lat: 47.0,
-                        lng: 8.0,
-                    }),
+                    PolygonSegment::Point(Coord::from_degrees(47.0, 8.0)),
                     PolygonSegment::ArcSegment(ArcSegment {
-                        centerpoint: Coord {
-                            lat: 47.0,
-                            lng: 8.5,
-                        },
+                        centerpoint: Coord::from_degrees(47.0, 8.5),
                         radius: 10.0,
                         angle_start: 270.0,
                         angle_end: 290.0,
@@ -675,6 +844,8 @@ mod tests {
             call_sign: None,
             transponder_code: None,
             activation_times: None,
+            label_points: vec![],
+            extensions: vec![],
         };
 
         insta::assert_snapshot!(write_airspace(&airspace), @r"
@@ -699,18 +870,9 @@ mod tests {
             upper_bound: Altitude::FlightLevel(50),
             geom: Geometry::Polygon {
                 segments: vec![PolygonSegment::Arc(Arc {
-                    centerpoint: Coord {
-                        lat: 47.0,
-                        lng: 8.0,
-                    },
-                    start: Coord {
-                        lat: 47.0,
-                        lng: 8.5,
-                    },
-                    end: Coord {
-                        lat: 47.5,
-                        lng: 8.0,
-                    },
+                    centerpoint: Coord::from_degrees(47.0, 8.0),
+                    start: Coord::from_degrees(47.0, 8.5),
+                    end: Coord::from_degrees(47.5, 8.0),
                     direction: Direction::Ccw,
                 })],
             },
@@ -718,6 +880,8 @@ mod tests {
             call_sign: None,
             transponder_code: None,
             activation_times: None,
+            label_points: vec![],
+            extensions: vec![],
         };
 
         insta::assert_snapshot!(write_airspace(&airspace), @r"
@@ -730,4 +894,97 @@ mod tests {
         DB 47:00:00 N 008:30:00 E, 47:30:00 N 008:00:00 E
         ");
     }
+
+    #[test]
+    fn contains_position() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let airspace = Airspace {
+            name: "TMA".to_string(),
+            class: Class::C,
+            type_: None,
+            lower_bound: Altitude::FeetAgl(1000),
+            upper_bound: Altitude::FlightLevel(100),
+            geom: Geometry::Circle {
+                centerpoint: center,
+                radius: 5.0,
+            },
+            frequency: None,
+            call_sign: None,
+            transponder_code: None,
+            activation_times: Some("2024-06-01T08:00Z/2024-06-01T16:00Z".parse().unwrap()),
+            label_points: vec![],
+            extensions: vec![],
+        };
+        let context = PositionContext {
+            qnh: Some(1013.25),
+            ground_elevation: Some(1500.0),
+            time: None,
+        };
+        let outside = Coord::from_degrees(48.0, 8.0);
+
+        let check = |coord: &Coord, altitude, context: &PositionContext| {
+            airspace.contains_position(coord, altitude, context)
+        };
+        assert_eq!(
+            check(&outside, 5000.0, &context),
+            Ok(Containment::OutsideHorizontally)
+        );
+        assert_eq!(
+            check(&center, 2000.0, &context),
+            Ok(Containment::Below { margin: 500.0 })
+        );
+        assert_eq!(
+            check(&center, 3000.0, &context),
+            Ok(Containment::Inside {
+                nearest: Limit::Lower,
+                margin: 500.0
+            })
+        );
+        assert_eq!(
+            check(&center, 9000.0, &context),
+            Ok(Containment::Inside {
+                nearest: Limit::Upper,
+                margin: 1000.0
+            })
+        );
+        assert_eq!(
+            check(&center, 10_500.0, &context),
+            Ok(Containment::Above { margin: 500.0 })
+        );
+        assert_eq!(
+            check(&center, 10_500.0, &context).unwrap().decisive_limit(),
+            Some(Limit::Upper)
+        );
+
+        // A lower QNH moves the flight level down
+        let low_qnh = PositionContext {
+            qnh: Some(993.0),
+            ..context
+        };
+        assert!(matches!(
+            check(&center, 9500.0, &low_qnh),
+            Ok(Containment::Above { .. })
+        ));
+
+        // Activation times
+        let at = |s: &str| PositionContext {
+            time: Some(iso8601::datetime(s).unwrap()),
+            ..context
+        };
+        assert_eq!(
+            check(&center, 3000.0, &at("2024-06-01T17:00Z")),
+            Ok(Containment::Inactive)
+        );
+        assert!(
+            check(&center, 3000.0, &at("2024-06-01T12:00Z"))
+                .unwrap()
+                .is_inside()
+        );
+
+        // AGL limits need the ground elevation
+        assert_eq!(
+            check(&center, 3000.0, &PositionContext::default()),
+            Err("Ground elevation required for altitude 1000 ft AGL".to_string())
+        );
+    }
 }
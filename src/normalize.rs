@@ -0,0 +1,352 @@
+use crate::{Geometry, PolygonSegment, geometry};
+
+/// What to do with the closing point of a polygon (a last point equal to
+/// the first one).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Closing {
+    /// Leave open and closed polygons as they are
+    #[default]
+    Keep,
+    /// Append a closing point to open polygons
+    Close,
+    /// Remove the closing point of closed polygons
+    Open,
+}
+
+/// Winding order of a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Orientation {
+    Clockwise,
+    Counterclockwise,
+}
+
+/// Options for [`Geometry::normalize`].
+///
+/// Zero-length edges are always removed. The default options leave the
+/// closing point and the orientation unchanged:
+///
+/// ```
+/// use openair::{Closing, NormalizeOptions, Orientation};
+///
+/// let options = NormalizeOptions {
+///     closing: Closing::Open,
+///     orientation: Some(Orientation::Counterclockwise),
+/// };
+/// # let _ = options;
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// Handling of the closing point
+    pub closing: Closing,
+    /// Orientation to enforce, if any
+    pub orientation: Option<Orientation>,
+}
+
+/// Changes made by [`Geometry::normalize`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct NormalizeReport {
+    /// Number of points removed because they were equal to an adjacent
+    /// point or arc end point
+    pub removed_points: usize,
+    /// True if a closing point was appended
+    pub closed: bool,
+    /// True if the closing point was removed
+    pub opened: bool,
+    /// True if the order of the segments was reversed
+    pub reversed: bool,
+}
+
+impl NormalizeReport {
+    /// Returns true if the geometry was not changed.
+    pub fn is_unchanged(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Geometry {
+    /// Returns the winding order of a polygon (see [`Self::area_m2`]), or
+    /// `None` for circles and polygons without area.
+    pub fn orientation(&self) -> Option<Orientation> {
+        let Self::Polygon { segments } = self else {
+            return None;
+        };
+        let area = geometry::signed_area(segments);
+        if area > 0.0 {
+            Some(Orientation::Clockwise)
+        } else if area < 0.0 {
+            Some(Orientation::Counterclockwise)
+        } else {
+            None
+        }
+    }
+
+    /// Normalize a polygon and report what was changed. Circles are left
+    /// unchanged.
+    ///
+    /// Points equal to the preceding point or arc end, or to the start of the
+    /// following arc, are removed. Afterwards the closing point is handled
+    /// and the orientation enforced as configured in the options. Reversing a
+    /// polygon also reverses the direction of its arcs, so the area stays the
+    /// same. A polygon whose last arc ends at its first point is closed
+    /// without a closing point, so [`Closing::Open`] leaves it unchanged.
+    pub fn normalize(&mut self, options: &NormalizeOptions) -> NormalizeReport {
+        let mut report = NormalizeReport::default();
+        let Self::Polygon { segments } = self else {
+            return report;
+        };
+
+        let original = std::mem::take(segments);
+        let original_len = original.len();
+        let mut iter = original.into_iter().peekable();
+        while let Some(segment) = iter.next() {
+            if let PolygonSegment::Point(point) = segment {
+                let equals_previous = segments
+                    .last()
+                    .is_some_and(|previous| previous.end_point() == point);
+                let equals_next_arc = iter.peek().is_some_and(|next| {
+                    !matches!(next, PolygonSegment::Point(_)) && next.start_point() == point
+                });
+                if equals_previous || equals_next_arc {
+                    continue;
+                }
+            }
+            segments.push(segment);
+        }
+        report.removed_points = original_len - segments.len();
+
+        if segments.len() > 1 {
+            let start = segments[0].start_point();
+            let last = &segments[segments.len() - 1];
+            let closed = last.end_point() == start;
+            match options.closing {
+                Closing::Close if !closed => {
+                    segments.push(PolygonSegment::Point(start));
+                    report.closed = true;
+                }
+                Closing::Open if closed && matches!(last, PolygonSegment::Point(_)) => {
+                    segments.pop();
+                    report.opened = true;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(orientation) = options.orientation
+            && self
+                .orientation()
+                .is_some_and(|current| current != orientation)
+            && let Self::Polygon { segments } = self
+        {
+            segments.reverse();
+            segments.iter_mut().for_each(PolygonSegment::reverse);
+            report.reversed = true;
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Arc, ArcSegment, Coord, Direction};
+
+    fn point(lat: f64, lng: f64) -> PolygonSegment {
+        PolygonSegment::Point(Coord::from_degrees(lat, lng))
+    }
+
+    fn polygon(segments: Vec<PolygonSegment>) -> Geometry {
+        Geometry::Polygon { segments }
+    }
+
+    #[test]
+    fn remove_duplicates() {
+        let mut geom = polygon(vec![
+            point(47.0, 8.0),
+            point(47.0, 8.0),
+            point(47.0, 9.0),
+            point(46.0, 9.0),
+            point(46.0, 9.0),
+            point(46.0, 9.0),
+            point(47.0, 8.0),
+        ]);
+        let report = geom.normalize(&NormalizeOptions::default());
+        assert_eq!(
+            report,
+            NormalizeReport {
+                removed_points: 3,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            geom,
+            polygon(vec![
+                point(47.0, 8.0),
+                point(47.0, 9.0),
+                point(46.0, 9.0),
+                point(47.0, 8.0),
+            ])
+        );
+
+        // Already normalized
+        assert!(geom.normalize(&NormalizeOptions::default()).is_unchanged());
+    }
+
+    #[test]
+    fn remove_arc_end_points() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let start = Coord::from_degrees(47.1, 8.0);
+        let end = Coord::from_degrees(47.0, 8.15);
+        let arc = || {
+            PolygonSegment::Arc(Arc {
+                centerpoint: center,
+                start,
+                end,
+                direction: Direction::Cw,
+            })
+        };
+        let mut geom = polygon(vec![
+            PolygonSegment::Point(center),
+            PolygonSegment::Point(start),
+            arc(),
+            PolygonSegment::Point(end),
+        ]);
+        let report = geom.normalize(&NormalizeOptions::default());
+        assert_eq!(report.removed_points, 2);
+        assert_eq!(geom, polygon(vec![PolygonSegment::Point(center), arc()]));
+    }
+
+    #[test]
+    fn closing() {
+        let open = || polygon(vec![point(47.0, 8.0), point(47.0, 9.0), point(46.0, 9.0)]);
+        let closed = || {
+            polygon(vec![
+                point(47.0, 8.0),
+                point(47.0, 9.0),
+                point(46.0, 9.0),
+                point(47.0, 8.0),
+            ])
+        };
+        let close = NormalizeOptions {
+            closing: Closing::Close,
+            ..Default::default()
+        };
+        let open_options = NormalizeOptions {
+            closing: Closing::Open,
+            ..Default::default()
+        };
+
+        let mut geom = open();
+        assert!(geom.normalize(&close).closed);
+        assert_eq!(geom, closed());
+        assert!(geom.normalize(&close).is_unchanged());
+
+        let mut geom = closed();
+        assert!(geom.normalize(&open_options).opened);
+        assert_eq!(geom, open());
+        assert!(geom.normalize(&open_options).is_unchanged());
+
+        // Closed by an arc ending at the first point
+        let center = Coord::from_degrees(47.0, 8.0);
+        let arc_closed = || {
+            polygon(vec![
+                PolygonSegment::Point(center),
+                PolygonSegment::ArcSegment(ArcSegment {
+                    centerpoint: center,
+                    radius: 5.0,
+                    angle_start: 0.0,
+                    angle_end: 90.0,
+                    direction: Direction::Cw,
+                }),
+                PolygonSegment::Arc(Arc {
+                    centerpoint: center,
+                    start: Coord::from_degrees(46.9, 8.0),
+                    end: center,
+                    direction: Direction::Cw,
+                }),
+            ])
+        };
+        for options in [close, open_options] {
+            let mut geom = arc_closed();
+            assert!(geom.normalize(&options).is_unchanged());
+            assert_eq!(geom, arc_closed());
+        }
+    }
+
+    #[test]
+    fn orientation() {
+        let clockwise = || {
+            polygon(vec![
+                point(47.0, 8.0),
+                point(47.0, 9.0),
+                point(46.0, 9.0),
+                point(47.0, 8.0),
+            ])
+        };
+        assert_eq!(clockwise().orientation(), Some(Orientation::Clockwise));
+
+        let mut geom = clockwise();
+        let report = geom.normalize(&NormalizeOptions {
+            orientation: Some(Orientation::Counterclockwise),
+            ..Default::default()
+        });
+        assert!(report.reversed);
+        assert_eq!(geom.orientation(), Some(Orientation::Counterclockwise));
+        assert_eq!(
+            geom,
+            polygon(vec![
+                point(47.0, 8.0),
+                point(46.0, 9.0),
+                point(47.0, 9.0),
+                point(47.0, 8.0),
+            ])
+        );
+
+        let circle = Geometry::Circle {
+            centerpoint: Coord::from_degrees(47.0, 8.0),
+            radius: 5.0,
+        };
+        assert_eq!(circle.orientation(), None);
+        assert_eq!(polygon(vec![point(47.0, 8.0)]).orientation(), None);
+    }
+
+    #[test]
+    fn reverse_arcs() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let mut geom = polygon(vec![
+            PolygonSegment::Point(center),
+            PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 5.0,
+                angle_start: 90.0,
+                angle_end: 0.0,
+                direction: Direction::Ccw,
+            }),
+        ]);
+        assert_eq!(geom.orientation(), Some(Orientation::Counterclockwise));
+        let area = geom.area_m2();
+
+        let report = geom.normalize(&NormalizeOptions {
+            orientation: Some(Orientation::Clockwise),
+            ..Default::default()
+        });
+        assert!(report.reversed);
+        assert_eq!(
+            geom,
+            polygon(vec![
+                PolygonSegment::ArcSegment(ArcSegment {
+                    centerpoint: center,
+                    radius: 5.0,
+                    angle_start: 0.0,
+                    angle_end: 90.0,
+                    direction: Direction::Cw,
+                }),
+                PolygonSegment::Point(center),
+            ])
+        );
+        assert!((geom.area_m2() - area).abs() < 1.0);
+    }
+}
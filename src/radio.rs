@@ -0,0 +1,299 @@
+use std::fmt;
+
+/// Lowest VHF airband voice channel in kHz.
+const AIRBAND_MIN_KHZ: u32 = 118_000;
+/// Highest VHF airband voice channel name in kHz.
+const AIRBAND_MAX_KHZ: u32 = 136_990;
+
+/// Channel spacing of a [`Frequency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelSpacing {
+    /// 25 kHz channel spacing
+    Khz25,
+    /// 8.33 kHz channel spacing
+    Khz8_33,
+}
+
+/// A VHF airband radio frequency (AF extension record).
+///
+/// [`Frequency::parse`] validates the value against the airband (118.000 –
+/// 136.990 MHz) and the 25 kHz / 8.33 kHz channel naming scheme, and the
+/// file parser rejects airspaces with invalid values. To keep UHF or other
+/// non-standard values, use [`Frequency::parse_unchecked`] and check them
+/// with [`Frequency::validate`]. The original text is kept, so that writing
+/// the frequency reproduces the input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Frequency {
+    khz: Option<u32>,
+    text: String,
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Frequency {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl Frequency {
+    /// Parse and validate a frequency in MHz (e.g. "124.705").
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let frequency = Self::parse_unchecked(data);
+        frequency.validate()?;
+        Ok(frequency)
+    }
+
+    /// Parse a frequency without validating it. Values that are not a number
+    /// in MHz with at most three decimals are kept as text.
+    pub fn parse_unchecked(data: &str) -> Self {
+        let text = data.trim();
+        Self {
+            khz: parse_khz(text),
+            text: text.to_string(),
+        }
+    }
+
+    /// Check that the frequency is a valid airband channel name.
+    pub fn validate(&self) -> Result<(), String> {
+        let text = &self.text;
+        let khz = self
+            .khz
+            .ok_or_else(|| format!("Invalid frequency: {text}"))?;
+        if !(AIRBAND_MIN_KHZ..=AIRBAND_MAX_KHZ).contains(&khz) {
+            return Err(format!("Frequency {text} is outside of the airband"));
+        }
+        if Self::spacing_of(khz).is_none() {
+            return Err(format!("Frequency {text} is not a valid channel"));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the frequency is a valid airband channel name.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Returns the channel name in kHz (e.g. 124705 for "124.705"), or `None`
+    /// if the value is not a number.
+    pub fn khz(&self) -> Option<u32> {
+        self.khz
+    }
+
+    /// Returns the channel name in MHz, or `None` if the value is not a
+    /// number.
+    pub fn mhz(&self) -> Option<f64> {
+        self.khz.map(|khz| f64::from(khz) / 1000.0)
+    }
+
+    /// Returns the original text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the channel spacing this channel name belongs to, or `None`
+    /// for invalid frequencies.
+    pub fn spacing(&self) -> Option<ChannelSpacing> {
+        self.khz
+            .filter(|khz| (AIRBAND_MIN_KHZ..=AIRBAND_MAX_KHZ).contains(khz))
+            .and_then(Self::spacing_of)
+    }
+
+    fn spacing_of(khz: u32) -> Option<ChannelSpacing> {
+        // In the 8.33 kHz scheme, the channel names ending in 20, 45, 70 and
+        // 95 are not used. Names that are multiples of 25 kHz refer to the
+        // 25 kHz channels.
+        match khz % 100 {
+            0 | 25 | 50 | 75 => Some(ChannelSpacing::Khz25),
+            5 | 10 | 15 | 30 | 35 | 40 | 55 | 60 | 65 | 80 | 85 | 90 => {
+                Some(ChannelSpacing::Khz8_33)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse a frequency in MHz with at most three decimals into kHz.
+fn parse_khz(text: &str) -> Option<u32> {
+    let (mhz, frac) = text.split_once('.').unwrap_or((text, ""));
+    if mhz.is_empty()
+        || frac.len() > 3
+        || !mhz.bytes().all(|b| b.is_ascii_digit())
+        || !frac.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let mhz = mhz.parse::<u32>().ok()?;
+    let frac = format!("{frac:0<3}").parse::<u32>().ok()?;
+    mhz.checked_mul(1000)?.checked_add(frac)
+}
+
+/// A transponder code (AX extension record).
+///
+/// Valid squawk codes consist of four octal digits (0000 – 7777). Like
+/// [`Frequency`], invalid values are rejected by the file parser and can
+/// only be kept explicitly (see [`Squawk::parse_unchecked`]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Squawk {
+    code: Option<u16>,
+    text: String,
+}
+
+impl fmt::Display for Squawk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Squawk {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.code {
+            Some(code) if self.is_valid() => serializer.serialize_u16(code),
+            _ => serializer.serialize_str(&self.text),
+        }
+    }
+}
+
+impl Squawk {
+    /// Create a squawk from its decimal representation (e.g. `7000`).
+    pub fn new(code: u16) -> Result<Self, String> {
+        Self::parse(&format!("{code:04}")).map_err(|_| format!("Invalid transponder code: {code}"))
+    }
+
+    /// Parse and validate a four digit squawk code.
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let squawk = Self::parse_unchecked(data);
+        squawk.validate()?;
+        Ok(squawk)
+    }
+
+    /// Parse a squawk code without validating it.
+    pub fn parse_unchecked(data: &str) -> Self {
+        let text = data.trim();
+        Self {
+            code: text.parse().ok(),
+            text: text.to_string(),
+        }
+    }
+
+    /// Check that the code consists of four octal digits.
+    pub fn validate(&self) -> Result<(), String> {
+        let text = &self.text;
+        if text.len() != 4 || !text.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+            return Err(format!("Invalid transponder code: {text}"));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the code consists of four octal digits.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Returns the code in its decimal representation (e.g. `7000`), or
+    /// `None` if the value is not a number.
+    pub fn code(&self) -> Option<u16> {
+        self.code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_compact_debug_snapshot;
+
+    use super::*;
+
+    #[test]
+    fn parse_frequency() {
+        assert_compact_debug_snapshot!(Frequency::parse("124.705"), @r#"Ok(Frequency { khz: Some(124705), text: "124.705" })"#);
+        assert_compact_debug_snapshot!(Frequency::parse("123.45"), @r#"Ok(Frequency { khz: Some(123450), text: "123.45" })"#);
+        assert_compact_debug_snapshot!(Frequency::parse("121.5"), @r#"Ok(Frequency { khz: Some(121500), text: "121.5" })"#);
+        assert_compact_debug_snapshot!(Frequency::parse("118"), @r#"Ok(Frequency { khz: Some(118000), text: "118" })"#);
+        assert_compact_debug_snapshot!(Frequency::parse(" 136.990 "), @r#"Ok(Frequency { khz: Some(136990), text: "136.990" })"#);
+    }
+
+    #[test]
+    fn parse_frequency_invalid() {
+        assert_compact_debug_snapshot!(Frequency::parse("TOWER"), @r#"Err("Invalid frequency: TOWER")"#);
+        assert_compact_debug_snapshot!(Frequency::parse("124.7055"), @r#"Err("Invalid frequency: 124.7055")"#);
+        assert_compact_debug_snapshot!(Frequency::parse("-124.7"), @r#"Err("Invalid frequency: -124.7")"#);
+        assert_compact_debug_snapshot!(Frequency::parse(".7"), @r#"Err("Invalid frequency: .7")"#);
+        assert_compact_debug_snapshot!(Frequency::parse("117.975"), @r#"Err("Frequency 117.975 is outside of the airband")"#);
+        assert_compact_debug_snapshot!(Frequency::parse("137.000"), @r#"Err("Frequency 137.000 is outside of the airband")"#);
+        assert_compact_debug_snapshot!(Frequency::parse("124.720"), @r#"Err("Frequency 124.720 is not a valid channel")"#);
+        assert_compact_debug_snapshot!(Frequency::parse("124.701"), @r#"Err("Frequency 124.701 is not a valid channel")"#);
+    }
+
+    #[test]
+    fn frequency_accessors() {
+        let freq = Frequency::parse("124.705").unwrap();
+        assert_eq!(freq.khz(), Some(124_705));
+        assert_eq!(freq.mhz(), Some(124.705));
+        assert_eq!(freq.as_str(), "124.705");
+        assert_eq!(freq.to_string(), "124.705");
+        assert_eq!(freq.spacing(), Some(ChannelSpacing::Khz8_33));
+        assert_eq!(
+            Frequency::parse("119.700").unwrap().spacing(),
+            Some(ChannelSpacing::Khz25)
+        );
+    }
+
+    #[test]
+    fn parse_frequency_unchecked() {
+        let uhf = Frequency::parse_unchecked("243.000");
+        assert_eq!(uhf.to_string(), "243.000");
+        assert_eq!(uhf.khz(), Some(243_000));
+        assert_eq!(uhf.spacing(), None);
+        assert!(!uhf.is_valid());
+        assert_eq!(
+            uhf.validate(),
+            Err("Frequency 243.000 is outside of the airband".to_string())
+        );
+
+        let text = Frequency::parse_unchecked("TOWER");
+        assert_eq!(text.as_str(), "TOWER");
+        assert_eq!(text.mhz(), None);
+        assert!(Frequency::parse_unchecked("124.705").is_valid());
+    }
+
+    #[test]
+    fn parse_squawk() {
+        assert_eq!(Squawk::parse("7000").unwrap().code(), Some(7000));
+        assert_eq!(Squawk::parse("0020").unwrap().code(), Some(20));
+        assert_eq!(Squawk::parse("0020").unwrap().to_string(), "0020");
+        assert_eq!(Squawk::parse("7777").unwrap().code(), Some(7777));
+    }
+
+    #[test]
+    fn parse_squawk_invalid() {
+        assert_compact_debug_snapshot!(Squawk::parse("9999"), @r#"Err("Invalid transponder code: 9999")"#);
+        assert_compact_debug_snapshot!(Squawk::parse("7080"), @r#"Err("Invalid transponder code: 7080")"#);
+        assert_compact_debug_snapshot!(Squawk::parse("700"), @r#"Err("Invalid transponder code: 700")"#);
+        assert_compact_debug_snapshot!(Squawk::parse("70000"), @r#"Err("Invalid transponder code: 70000")"#);
+        assert_compact_debug_snapshot!(Squawk::parse("A000"), @r#"Err("Invalid transponder code: A000")"#);
+    }
+
+    #[test]
+    fn parse_squawk_unchecked() {
+        let squawk = Squawk::parse_unchecked("7080");
+        assert_eq!(squawk.to_string(), "7080");
+        assert_eq!(squawk.code(), Some(7080));
+        assert!(!squawk.is_valid());
+        assert_eq!(Squawk::parse_unchecked("A000").code(), None);
+        assert!(Squawk::parse_unchecked("0020").is_valid());
+    }
+
+    #[test]
+    fn new_squawk() {
+        assert_eq!(Squawk::new(7000).unwrap().code(), Some(7000));
+        assert_eq!(Squawk::new(0).unwrap().to_string(), "0000");
+        assert!(Squawk::new(7008).is_err());
+        assert!(Squawk::new(17000).is_err());
+    }
+}
@@ -1,14 +1,23 @@
 use std::io::Write;
 
-use log::trace;
+use log::{trace, warn};
 
 use crate::{
-    activations::ActivationTimes, altitude::Altitude, classes::Class, coords::Coord,
+    activations::ActivationTimes,
+    airspace_type::AirspaceType,
+    altitude::Altitude,
+    classes::Class,
+    coords::Coord,
     geometry::Direction,
+    radio::{Frequency, Squawk},
+    writer::{TypeSpelling, WriterOptions},
 };
 
 /// Validate an angle is in the range 0..360.
 fn validate_angle(val: f32) -> Result<f32, String> {
+    if val.is_nan() {
+        return Err(format!("Angle {val} is not a number"));
+    }
     if val > 360.0 {
         return Err(format!("Angle {val} too large"));
     }
@@ -18,6 +27,16 @@ fn validate_angle(val: f32) -> Result<f32, String> {
     Ok(val)
 }
 
+fn validate_radius(val: f32) -> Result<f32, String> {
+    if !val.is_finite() {
+        return Err(format!("Radius {val} is not finite"));
+    }
+    if val <= 0.0 {
+        return Err(format!("Radius {val} is not positive"));
+    }
+    Ok(val)
+}
+
 /// A parsed OpenAir record from a single line.
 #[derive(Debug, PartialEq)]
 pub enum Record<'a> {
@@ -28,12 +47,18 @@ pub enum Record<'a> {
     UpperBound(Altitude),
 
     // Extension records
-    AirspaceType(&'a str),
-    Frequency(&'a str),
+    AirspaceType(AirspaceType),
+    Frequency(Frequency),
     CallSign(&'a str),
-    TransponderCode(u16),
+    TransponderCode(Squawk),
     ActivationTimes(ActivationTimes),
-    UnknownExtension(&'a str),
+    UnknownExtension {
+        tag: &'a str,
+        data: &'a str,
+    },
+
+    // Label placement hint
+    LabelPlacement(Coord),
 
     // Variable records
     VarX(Coord),
@@ -55,9 +80,10 @@ pub enum Record<'a> {
     // Ignored records (no payload)
     Empty,
     Comment,
-    LabelPlacement,
     Pen,
     Brush,
+    /// An `AT` record with invalid coordinates
+    InvalidLabelPlacement,
 }
 
 impl<'a> Record<'a> {
@@ -75,67 +101,83 @@ impl<'a> Record<'a> {
                 | Record::CallSign(_)
                 | Record::TransponderCode(_)
                 | Record::ActivationTimes(_)
-                | Record::UnknownExtension(_)
+                | Record::UnknownExtension { .. }
         )
     }
 
-    /// Writes the record in OpenAir format with CRLF line ending.
-    pub fn write<W: Write>(self, mut writer: W) -> std::io::Result<()> {
+    /// Writes the record in OpenAir format using the given options.
+    pub fn write<W: Write>(self, mut writer: W, options: &WriterOptions) -> std::io::Result<()> {
+        let eol = options.line_ending.as_str();
         match self {
-            Record::AirspaceClass(class) => write!(writer, "AC {}\r\n", class.to_str()),
-            Record::AirspaceType(ty) => write!(writer, "AY {ty}\r\n"),
-            Record::AirspaceName(name) => write!(writer, "AN {name}\r\n"),
+            Record::AirspaceClass(class) => write!(writer, "AC {}{eol}", class.to_str()),
+            Record::AirspaceType(ty) => {
+                let ty = match options.type_spelling {
+                    TypeSpelling::Long => ty.to_str(),
+                    TypeSpelling::Short => ty.to_short_str(),
+                };
+                write!(writer, "AY {ty}{eol}")
+            }
+            Record::AirspaceName(name) => write!(writer, "AN {name}{eol}"),
             Record::LowerBound(alt) => {
                 write!(writer, "AL ")?;
-                alt.write(&mut writer)?;
-                write!(writer, "\r\n")
+                alt.write_with(&mut writer, options)?;
+                write!(writer, "{eol}")
             }
             Record::UpperBound(alt) => {
                 write!(writer, "AH ")?;
-                alt.write(&mut writer)?;
-                write!(writer, "\r\n")
+                alt.write_with(&mut writer, options)?;
+                write!(writer, "{eol}")
             }
-            Record::Frequency(freq) => write!(writer, "AF {freq}\r\n"),
-            Record::CallSign(sign) => write!(writer, "AG {sign}\r\n"),
-            Record::TransponderCode(code) => write!(writer, "AX {code}\r\n"),
+            Record::Frequency(freq) => write!(writer, "AF {freq}{eol}"),
+            Record::CallSign(sign) => write!(writer, "AG {sign}{eol}"),
+            Record::TransponderCode(code) => write!(writer, "AX {code}{eol}"),
             Record::ActivationTimes(times) => {
                 write!(writer, "AA ")?;
                 times.write(&mut writer)?;
-                write!(writer, "\r\n")
+                write!(writer, "{eol}")
             }
-            Record::UnknownExtension(ext) => write!(writer, "{ext}\r\n"),
+            Record::UnknownExtension { tag, data: "" } => write!(writer, "{tag}{eol}"),
+            Record::UnknownExtension { tag, data } => write!(writer, "{tag} {data}{eol}"),
             Record::VarX(coord) => {
                 write!(writer, "V X=")?;
-                coord.write(&mut writer)?;
-                write!(writer, "\r\n")
+                coord.write_with(&mut writer, options)?;
+                write!(writer, "{eol}")
             }
             Record::VarD(direction) => {
                 let dir_str = match direction {
                     Direction::Cw => "+",
                     Direction::Ccw => "-",
                 };
-                write!(writer, "V D={dir_str}\r\n")
+                write!(writer, "V D={dir_str}{eol}")
+            }
+            Record::LabelPlacement(coord) => {
+                write!(writer, "AT ")?;
+                coord.write_with(&mut writer, options)?;
+                write!(writer, "{eol}")
             }
             Record::Point(coord) => {
                 write!(writer, "DP ")?;
-                coord.write(&mut writer)?;
-                write!(writer, "\r\n")
+                coord.write_with(&mut writer, options)?;
+                write!(writer, "{eol}")
             }
-            Record::CircleRadius(radius) => write!(writer, "DC {radius}\r\n"),
+            Record::CircleRadius(radius) => write!(writer, "DC {radius}{eol}"),
             Record::ArcSegmentData {
                 radius,
                 angle_start,
                 angle_end,
-            } => write!(writer, "DA {radius}, {angle_start}, {angle_end}\r\n"),
+            } => {
+                let sep = options.arc_spacing.as_str();
+                write!(writer, "DA {radius}{sep}{angle_start}{sep}{angle_end}{eol}")
+            }
             Record::ArcData { start, end } => {
                 write!(writer, "DB ")?;
-                start.write(&mut writer)?;
-                write!(writer, ", ")?;
-                end.write(&mut writer)?;
-                write!(writer, "\r\n")
+                start.write_with(&mut writer, options)?;
+                write!(writer, "{}", options.arc_spacing.as_str())?;
+                end.write_with(&mut writer, options)?;
+                write!(writer, "{eol}")
             }
-            Record::Empty => write!(writer, "\r\n"),
-            Record::Comment | Record::LabelPlacement | Record::Pen | Record::Brush => {
+            Record::Empty => write!(writer, "{eol}"),
+            Record::Comment | Record::Pen | Record::Brush | Record::InvalidLabelPlacement => {
                 // These records are ignored when writing
                 Ok(())
             }
@@ -154,7 +196,11 @@ impl<'a> Record<'a> {
         let mut chars = trimmed.chars().filter(|c: &char| !c.is_ascii_whitespace());
         let t1 = chars.next().ok_or_else(|| "Line too short".to_string())?;
         let t2 = chars.next().unwrap_or(' ');
-        let data = trimmed.split_once(' ').map(|x| x.1).unwrap_or("").trim();
+        let data = trimmed
+            .split_once(char::is_whitespace)
+            .map(|x| x.1)
+            .unwrap_or("")
+            .trim();
 
         trace!("Input: \"{:1}{:1}\"", t1, t2);
         match (t1, t2) {
@@ -183,25 +229,33 @@ impl<'a> Record<'a> {
                 Ok(Record::UpperBound(altitude))
             }
             ('A', 'T') => {
-                trace!("-> Label placement hint, ignore");
-                Ok(Record::LabelPlacement)
+                trace!("-> Found label placement hint");
+                // Label placement hints are optional, so skip them instead of
+                // failing the airspace
+                match Coord::parse_record(data) {
+                    Ok(coord) => Ok(Record::LabelPlacement(coord)),
+                    Err(error) => {
+                        warn!("Ignoring label placement hint: {error}");
+                        Ok(Record::InvalidLabelPlacement)
+                    }
+                }
             }
             ('A', 'Y') => {
-                trace!("-> Found type: {}", data);
-                Ok(Record::AirspaceType(data))
+                let type_ = AirspaceType::parse(data);
+                trace!("-> Found type: {}", type_);
+                Ok(Record::AirspaceType(type_))
             }
             ('A', 'F') => {
-                trace!("-> Found frequency: {}", data);
-                Ok(Record::Frequency(data))
+                let frequency = Frequency::parse(data)?;
+                trace!("-> Found frequency: {}", frequency);
+                Ok(Record::Frequency(frequency))
             }
             ('A', 'G') => {
                 trace!("-> Found call sign: {}", data);
                 Ok(Record::CallSign(data))
             }
             ('A', 'X') => {
-                let transponder_code = data
-                    .parse()
-                    .map_err(|_| format!("Invalid transponder code: {}", data))?;
+                let transponder_code = Squawk::parse(data)?;
                 trace!("-> Found transponder code: {}", transponder_code);
                 Ok(Record::TransponderCode(transponder_code))
             }
@@ -212,7 +266,10 @@ impl<'a> Record<'a> {
             }
             ('A', _) => {
                 trace!("-> Found unknown extension record: {}", trimmed);
-                Ok(Record::UnknownExtension(trimmed))
+                let tag = trimmed
+                    .split_once(char::is_whitespace)
+                    .map_or(trimmed, |x| x.0);
+                Ok(Record::UnknownExtension { tag, data })
             }
             ('S', 'P') => {
                 trace!("-> Pen, ignore");
@@ -224,7 +281,7 @@ impl<'a> Record<'a> {
             }
             ('V', 'X') => {
                 trace!("-> Found X variable");
-                let coord = Coord::parse(data.get(2..).unwrap_or(""))?;
+                let coord = Coord::parse_record(data.get(2..).unwrap_or(""))?;
                 Ok(Record::VarX(coord))
             }
             ('V', 'D') => {
@@ -234,7 +291,7 @@ impl<'a> Record<'a> {
             }
             ('D', 'P') => {
                 trace!("-> Found point");
-                let coord = Coord::parse(data)?;
+                let coord = Coord::parse_record(data)?;
                 Ok(Record::Point(coord))
             }
             ('D', 'C') => {
@@ -242,6 +299,7 @@ impl<'a> Record<'a> {
                 let radius = data
                     .parse::<f32>()
                     .map_err(|_| format!("Invalid radius: {data}"))?;
+                let radius = validate_radius(radius)?;
                 Ok(Record::CircleRadius(radius))
             }
             ('D', 'A') => {
@@ -256,7 +314,7 @@ impl<'a> Record<'a> {
                 if parts.len() != 3 {
                     return Err(errmsg());
                 }
-                let radius = parts[0];
+                let radius = validate_radius(parts[0])?;
                 let angle_start = validate_angle(parts[1])?;
                 let angle_end = validate_angle(parts[2])?;
                 Ok(Record::ArcSegmentData {
@@ -271,7 +329,7 @@ impl<'a> Record<'a> {
                 let parts: Vec<Coord> = data
                     .split(',')
                     .map(str::trim)
-                    .map(Coord::parse)
+                    .map(Coord::parse_record)
                     .collect::<Result<Vec<Coord>, _>>()
                     .map_err(|_| errmsg())?;
                 if parts.len() != 2 {
@@ -339,9 +397,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_invalid_radius() {
+        assert_compact_debug_snapshot!(
+            Record::parse("DC inf"),
+            @r#"Err("Radius inf is not finite")"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("DC 1e39"),
+            @r#"Err("Radius inf is not finite")"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("DC 0"),
+            @r#"Err("Radius 0 is not positive")"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("DA -5,0,90"),
+            @r#"Err("Radius -5 is not positive")"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("DA NaN,0,90"),
+            @r#"Err("Radius NaN is not finite")"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("DA 5,nan,90"),
+            @r#"Err("Angle NaN is not a number")"#,
+        );
+    }
+
+    #[test]
+    fn parse_invalid_label_placement() {
+        assert_compact_debug_snapshot!(
+            Record::parse("AT foo"),
+            @"Ok(InvalidLabelPlacement)",
+        );
+    }
+
+    #[test]
+    fn parse_unknown_extension() {
+        assert_compact_debug_snapshot!(
+            Record::parse("AZ custom  data "),
+            @r#"Ok(UnknownExtension { tag: "AZ", data: "custom  data" })"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("A*"),
+            @r#"Ok(UnknownExtension { tag: "A*", data: "" })"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("AZ\tvendor"),
+            @r#"Ok(UnknownExtension { tag: "AZ", data: "vendor" })"#,
+        );
+    }
+
+    #[test]
+    fn parse_tab_separated() {
+        assert_compact_debug_snapshot!(Record::parse("AN\tZone 1"), @r#"Ok(AirspaceName("Zone 1"))"#);
+        assert_compact_debug_snapshot!(Record::parse("DC\t5"), @"Ok(CircleRadius(5.0))");
+    }
+
+    #[test]
+    fn parse_invalid_radio_records() {
+        assert_compact_debug_snapshot!(
+            Record::parse("AF 124.720"),
+            @r#"Err("Frequency 124.720 is not a valid channel")"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("AF 243.000"),
+            @r#"Err("Frequency 243.000 is outside of the airband")"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("AX 9999"),
+            @r#"Err("Invalid transponder code: 9999")"#,
+        );
+        assert_compact_debug_snapshot!(
+            Record::parse("AX ABCD"),
+            @r#"Err("Invalid transponder code: ABCD")"#,
+        );
+    }
+
     fn write_record(record: Record) -> String {
         let mut buf = Vec::new();
-        record.write(&mut buf).unwrap();
+        record.write(&mut buf, &WriterOptions::default()).unwrap();
         String::from_utf8(buf).unwrap()
     }
 
@@ -356,7 +492,10 @@ mod tests {
 
     #[test]
     fn write_airspace_type() {
-        assert_eq!(write_record(Record::AirspaceType("MATZ")), "AY MATZ\r\n");
+        assert_eq!(
+            write_record(Record::AirspaceType(AirspaceType::Matz)),
+            "AY MATZ\r\n"
+        );
     }
 
     #[test]
@@ -381,7 +520,8 @@ mod tests {
 
     #[test]
     fn write_frequency() {
-        assert_eq!(write_record(Record::Frequency("123.45")), "AF 123.45\r\n");
+        let freq = Frequency::parse("123.45").unwrap();
+        assert_eq!(write_record(Record::Frequency(freq)), "AF 123.45\r\n");
     }
 
     #[test]
@@ -391,7 +531,8 @@ mod tests {
 
     #[test]
     fn write_transponder_code() {
-        assert_eq!(write_record(Record::TransponderCode(7000)), "AX 7000\r\n");
+        let code = Squawk::new(7000).unwrap();
+        assert_eq!(write_record(Record::TransponderCode(code)), "AX 7000\r\n");
     }
 
     #[test]
@@ -406,23 +547,43 @@ mod tests {
     #[test]
     fn write_unknown_extension() {
         assert_eq!(
-            write_record(Record::UnknownExtension("AZ custom data")),
+            write_record(Record::UnknownExtension {
+                tag: "AZ",
+                data: "custom data"
+            }),
             "AZ custom data\r\n"
         );
     }
 
+    #[test]
+    fn write_unknown_extension_without_data() {
+        assert_eq!(
+            write_record(Record::UnknownExtension {
+                tag: "AZ",
+                data: ""
+            }),
+            "AZ\r\n"
+        );
+    }
+
     #[test]
     fn write_var_x() {
-        let coord = Coord {
-            lat: 46.86222222222222,
-            lng: 9.328333333333333,
-        };
+        let coord = Coord::from_degrees(46.86222222222222, 9.328333333333333);
         assert_eq!(
             write_record(Record::VarX(coord)),
             "V X=46:51:44 N 009:19:42 E\r\n"
         );
     }
 
+    #[test]
+    fn write_label_placement() {
+        let coord = Coord::from_degrees(46.86222222222222, 9.328333333333333);
+        assert_eq!(
+            write_record(Record::LabelPlacement(coord)),
+            "AT 46:51:44 N 009:19:42 E\r\n"
+        );
+    }
+
     #[test]
     fn write_var_d() {
         assert_eq!(write_record(Record::VarD(Direction::Cw)), "V D=+\r\n");
@@ -431,10 +592,7 @@ mod tests {
 
     #[test]
     fn write_point() {
-        let coord = Coord {
-            lat: 46.86222222222222,
-            lng: 9.328333333333333,
-        };
+        let coord = Coord::from_degrees(46.86222222222222, 9.328333333333333);
         assert_eq!(
             write_record(Record::Point(coord)),
             "DP 46:51:44 N 009:19:42 E\r\n"
@@ -460,14 +618,8 @@ mod tests {
 
     #[test]
     fn write_arc_data() {
-        let start = Coord {
-            lat: 46.86222222222222,
-            lng: 9.328333333333333,
-        };
-        let end = Coord {
-            lat: 47.0,
-            lng: 9.5,
-        };
+        let start = Coord::from_degrees(46.86222222222222, 9.328333333333333);
+        let end = Coord::from_degrees(47.0, 9.5);
         assert_eq!(
             write_record(Record::ArcData { start, end }),
             "DB 46:51:44 N 009:19:42 E, 47:00:00 N 009:30:00 E\r\n"
@@ -482,7 +634,6 @@ mod tests {
     #[test]
     fn write_ignored_records() {
         assert_eq!(write_record(Record::Comment), "");
-        assert_eq!(write_record(Record::LabelPlacement), "");
         assert_eq!(write_record(Record::Pen), "");
         assert_eq!(write_record(Record::Brush), "");
     }
@@ -0,0 +1,423 @@
+use crate::{Airspace, AirspaceType, Class};
+
+/// Conditions of a [`RemapRule`]. All conditions that are set must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemapMatch {
+    /// Match airspaces with this class
+    pub class: Option<Class>,
+    /// Match airspaces with this type (`Some(None)` matches a missing type)
+    pub type_: Option<Option<AirspaceType>>,
+    /// Match airspaces whose name matches this pattern (`*` and `?` wildcards)
+    pub name: Option<String>,
+}
+
+impl RemapMatch {
+    /// Returns true if the airspace matches all conditions.
+    pub fn matches(&self, airspace: &Airspace) -> bool {
+        if let Some(class) = self.class
+            && class != airspace.class
+        {
+            return false;
+        }
+        if let Some(ref type_) = self.type_
+            && *type_ != airspace.type_
+        {
+            return false;
+        }
+        if let Some(ref pattern) = self.name
+            && !glob_match(pattern, &airspace.name)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// A single remapping rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemapRule {
+    /// Conditions that must match
+    pub matches: RemapMatch,
+    /// The new class
+    pub class: Option<Class>,
+    /// The new type (`Some(None)` removes the type)
+    pub type_: Option<Option<AirspaceType>>,
+}
+
+impl RemapRule {
+    /// Parse a single rule in config format (see [`RemapTable`]).
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let (conditions, actions) = data
+            .split_once("->")
+            .ok_or_else(|| format!("Missing \"->\" in remap rule: {data}"))?;
+
+        let mut rule = RemapRule::default();
+        for (key, value) in parse_assignments(conditions)? {
+            match key {
+                "class" => rule.matches.class = Some(Class::parse(&value)?),
+                "type" => rule.matches.type_ = Some(parse_type(&value)),
+                "name" => rule.matches.name = Some(value),
+                other => return Err(format!("Unknown remap condition: {other}")),
+            }
+        }
+        for (key, value) in parse_assignments(actions)? {
+            match key {
+                "class" => rule.class = Some(Class::parse(&value)?),
+                "type" => rule.type_ = Some(parse_type(&value)),
+                other => return Err(format!("Unknown remap action: {other}")),
+            }
+        }
+        if rule.class.is_none() && rule.type_.is_none() {
+            return Err(format!("Remap rule without actions: {data}"));
+        }
+        Ok(rule)
+    }
+}
+
+/// An ordered list of remapping rules.
+///
+/// Different producers encode the same concept differently (e.g. OpenAIP uses
+/// `AC UNC` together with `AY OFR`, others use `AC R`), and some devices only
+/// understand a subset of the classes (e.g. no `AC RMZ`). A remap table
+/// rewrites the class and type of airspaces based on a list of rules.
+///
+/// ## Config format
+///
+/// Every non-empty line that doesn't start with `#` contains one rule. A rule
+/// consists of conditions and actions, separated by `->`:
+///
+/// ```text
+/// # OpenAIP overflight restrictions
+/// class=UNC type=OFR -> class=R
+/// # Devices without RMZ support
+/// class=RMZ -> class=G type=RMZ
+/// name="*SEGELFLUG*" -> type=GSEC
+/// type=- -> type=UNCLASSIFIED
+/// ```
+///
+/// Conditions can be `class=<AC value>`, `type=<AY value>` and
+/// `name=<pattern>`. Name patterns are matched case-insensitively and support
+/// the wildcards `*` and `?`. Values containing whitespace must be quoted. The
+/// type value `-` stands for "no type". Actions can be `class=<AC value>` and
+/// `type=<AY value>`.
+///
+/// Rules are evaluated in order, the first matching rule wins.
+///
+/// ## Usage
+///
+/// Apply the table to parsed airspaces while reading, or pass it to
+/// [`WriterOptions::remap`](crate::WriterOptions::remap) to apply it while
+/// writing:
+///
+/// ```
+/// # use std::io::Cursor;
+/// use openair::{Class, RemapTable};
+///
+/// let table = RemapTable::parse("class=UNC -> class=G").unwrap();
+/// let mut reader = Cursor::new("AC UNC\nAN Zone\nAL GND\nAH FL100\nV X=47:00:00 N 008:00:00 E\nDC 1\n");
+/// let airspaces = table
+///     .remap(openair::parse(&mut reader))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(airspaces[0].class, Class::G);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemapTable {
+    rules: Vec<RemapRule>,
+}
+
+impl RemapTable {
+    /// Create an empty table, which leaves every airspace unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a remap table in config format, one rule per line.
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let mut table = Self::new();
+        for (i, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let rule = RemapRule::parse(line).map_err(|e| format!("Line {}: {e}", i + 1))?;
+            table.push(rule);
+        }
+        Ok(table)
+    }
+
+    /// Append a rule to the table.
+    pub fn push(&mut self, rule: RemapRule) {
+        self.rules.push(rule);
+    }
+
+    /// Returns the rules of this table.
+    pub fn rules(&self) -> &[RemapRule] {
+        &self.rules
+    }
+
+    /// Apply the first matching rule to the airspace.
+    ///
+    /// Returns true if a rule matched.
+    pub fn apply(&self, airspace: &mut Airspace) -> bool {
+        let Some(rule) = self.find(airspace) else {
+            return false;
+        };
+        if let Some(class) = rule.class {
+            airspace.class = class;
+        }
+        if let Some(ref type_) = rule.type_ {
+            airspace.type_ = type_.clone();
+        }
+        true
+    }
+
+    /// Returns the class and type the airspace would have after
+    /// [`apply`](Self::apply), without modifying it.
+    pub fn remapped(&self, airspace: &Airspace) -> (Class, Option<AirspaceType>) {
+        let rule = self.find(airspace);
+        let class = rule.and_then(|r| r.class).unwrap_or(airspace.class);
+        let type_ = match rule.and_then(|r| r.type_.as_ref()) {
+            Some(type_) => type_.clone(),
+            None => airspace.type_.clone(),
+        };
+        (class, type_)
+    }
+
+    fn find(&self, airspace: &Airspace) -> Option<&RemapRule> {
+        self.rules.iter().find(|r| r.matches.matches(airspace))
+    }
+
+    /// Apply the table to every airspace returned by an iterator, e.g. the
+    /// one returned by [`parse`](crate::parse).
+    pub fn remap<'a, I>(
+        &'a self,
+        airspaces: I,
+    ) -> impl Iterator<Item = Result<Airspace, String>> + 'a
+    where
+        I: IntoIterator<Item = Result<Airspace, String>>,
+        I::IntoIter: 'a,
+    {
+        airspaces.into_iter().map(move |result| {
+            result.map(|mut airspace| {
+                self.apply(&mut airspace);
+                airspace
+            })
+        })
+    }
+}
+
+fn parse_type(value: &str) -> Option<AirspaceType> {
+    if value == "-" {
+        None
+    } else {
+        Some(AirspaceType::parse(value))
+    }
+}
+
+/// Split `key=value key="quoted value"` into key/value pairs.
+fn parse_assignments(data: &str) -> Result<Vec<(&str, String)>, String> {
+    let mut result = Vec::new();
+    let mut rest = data.trim_start();
+    while !rest.is_empty() {
+        let (key, after_key) = rest
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value: {}", rest.trim()))?;
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("Invalid key: {key}"));
+        }
+        let (value, after_value) = if let Some(quoted) = after_key.strip_prefix('"') {
+            quoted
+                .split_once('"')
+                .ok_or_else(|| format!("Unterminated quote: {after_key}"))?
+        } else {
+            let end = after_key
+                .find(char::is_whitespace)
+                .unwrap_or(after_key.len());
+            after_key.split_at(end)
+        };
+        if value.is_empty() {
+            return Err(format!("Missing value for {key}"));
+        }
+        result.push((key, value.to_string()));
+        rest = after_value.trim_start();
+    }
+    Ok(result)
+}
+
+/// Case-insensitive glob matching with `*` and `?` wildcards.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Altitude, Coord, Geometry};
+
+    fn airspace(name: &str, class: Class, type_: Option<AirspaceType>) -> Airspace {
+        Airspace {
+            name: name.to_string(),
+            class,
+            type_,
+            lower_bound: Altitude::Gnd,
+            upper_bound: Altitude::FlightLevel(100),
+            geom: Geometry::Circle {
+                centerpoint: Coord::from_degrees(47.0, 8.0),
+                radius: 5.0,
+            },
+            frequency: None,
+            call_sign: None,
+            transponder_code: None,
+            activation_times: None,
+            label_points: vec![],
+            extensions: vec![],
+        }
+    }
+
+    #[test]
+    fn glob() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*SEGELFLUG*", "Segelfluggelände Amlikon"));
+        assert!(glob_match("LF-R?? *", "LF-R17 E1"));
+        assert!(glob_match("a*b*c", "aXXbYYc"));
+        assert!(!glob_match("a*b*c", "aXXbYY"));
+        assert!(!glob_match("LF-R?", "LF-R17"));
+    }
+
+    #[test]
+    fn parse_rule() {
+        let rule =
+            RemapRule::parse(r#"class=UNC type=OFR name="* NID *" -> class=R type=-"#).unwrap();
+        assert_eq!(
+            rule,
+            RemapRule {
+                matches: RemapMatch {
+                    class: Some(Class::Unclassified),
+                    type_: Some(Some(AirspaceType::OverflightRestriction)),
+                    name: Some("* NID *".to_string()),
+                },
+                class: Some(Class::Restricted),
+                type_: Some(None),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_rule_invalid() {
+        assert_eq!(
+            RemapRule::parse("class=UNC"),
+            Err("Missing \"->\" in remap rule: class=UNC".to_string())
+        );
+        assert_eq!(
+            RemapRule::parse("class=UNC ->"),
+            Err("Remap rule without actions: class=UNC ->".to_string())
+        );
+        assert_eq!(
+            RemapRule::parse("color=red -> class=R"),
+            Err("Unknown remap condition: color".to_string())
+        );
+        assert_eq!(
+            RemapRule::parse("class=XYZ -> class=R"),
+            Err("Invalid class: XYZ".to_string())
+        );
+        assert_eq!(
+            RemapRule::parse(r#"name="abc -> class=R"#),
+            Err("Unterminated quote: \"abc ".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_table() {
+        let table = RemapTable::parse(
+            "# comment\n\nclass=UNC type=OFR -> class=R\n  class=RMZ -> class=G type=RMZ\n",
+        )
+        .unwrap();
+        assert_eq!(table.rules().len(), 2);
+
+        assert_eq!(
+            RemapTable::parse("class=RMZ -> class=G\nfoo\n"),
+            Err("Line 2: Missing \"->\" in remap rule: foo".to_string())
+        );
+    }
+
+    #[test]
+    fn apply_first_match() {
+        let table = RemapTable::parse(
+            "class=UNC type=OFR -> class=R\nclass=UNC -> class=G type=-\nname=*glider* -> type=GSEC",
+        )
+        .unwrap();
+
+        let mut a = airspace(
+            "Nest",
+            Class::Unclassified,
+            Some(AirspaceType::OverflightRestriction),
+        );
+        assert!(table.apply(&mut a));
+        assert_eq!(a.class, Class::Restricted);
+        assert_eq!(a.type_, Some(AirspaceType::OverflightRestriction));
+
+        let mut b = airspace("FIS", Class::Unclassified, Some(AirspaceType::Fis));
+        assert!(table.apply(&mut b));
+        assert_eq!(b.class, Class::G);
+        assert_eq!(b.type_, None);
+
+        let mut c = airspace("Glider Sector 1", Class::WaveWindow, None);
+        assert!(table.apply(&mut c));
+        assert_eq!(c.class, Class::WaveWindow);
+        assert_eq!(c.type_, Some(AirspaceType::GlidingSector));
+
+        let mut d = airspace("CTR", Class::Ctr, None);
+        assert!(!table.apply(&mut d));
+        assert_eq!(d.class, Class::Ctr);
+    }
+
+    #[test]
+    fn remapped_without_modifying() {
+        let table =
+            RemapTable::parse("class=UNC type=OFR -> class=R\nclass=UNC -> type=-").unwrap();
+
+        let a = airspace(
+            "Nest",
+            Class::Unclassified,
+            Some(AirspaceType::OverflightRestriction),
+        );
+        assert_eq!(
+            table.remapped(&a),
+            (Class::Restricted, Some(AirspaceType::OverflightRestriction))
+        );
+        assert_eq!(a.class, Class::Unclassified);
+
+        let b = airspace("FIS", Class::Unclassified, Some(AirspaceType::Fis));
+        assert_eq!(table.remapped(&b), (Class::Unclassified, None));
+
+        let c = airspace("CTR", Class::Ctr, Some(AirspaceType::Ctr));
+        assert_eq!(table.remapped(&c), (Class::Ctr, Some(AirspaceType::Ctr)));
+    }
+}
@@ -0,0 +1,371 @@
+use crate::{Airspace, Coord, Geometry, Orientation, PolygonSegment, geodesy, geometry};
+
+/// How polygons may change when simplified with [`Geometry::simplify_with`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SimplifyMode {
+    /// The boundary may move inwards or outwards
+    #[default]
+    Any,
+    /// The boundary may only move outwards, so that the simplified polygon
+    /// covers the original one. Points are kept if removing them would cut
+    /// off a part of the polygon.
+    Outward,
+}
+
+impl Geometry {
+    /// Remove polygon points whose removal moves the boundary by at most
+    /// `max_error` metres, using the Douglas–Peucker algorithm. Returns the
+    /// number of removed points.
+    ///
+    /// Only runs of points between arcs are simplified, arcs and their end
+    /// points are kept unchanged. The first and the last segment of a polygon
+    /// are always kept, as well as enough points to keep an area. Circles are
+    /// left unchanged.
+    pub fn simplify(&mut self, max_error: f64) -> usize {
+        self.simplify_with(max_error, SimplifyMode::Any)
+    }
+
+    /// Simplify the polygon like [`Self::simplify`], optionally making sure
+    /// that the boundary only moves outwards.
+    ///
+    /// ```
+    /// use openair::{Coord, Geometry, PolygonSegment, SimplifyMode};
+    ///
+    /// let point = |lat, lng| PolygonSegment::Point(Coord::from_degrees(lat, lng));
+    /// let mut geom = Geometry::Polygon {
+    ///     segments: vec![
+    ///         point(47.0, 8.0),
+    ///         point(47.0, 8.5),
+    ///         point(46.9999, 9.0), // dent, about 11 m inwards
+    ///         point(47.0, 9.5),
+    ///         point(46.0, 9.5),
+    ///         point(46.0, 8.0),
+    ///     ],
+    /// };
+    /// let area = geom.area_m2();
+    /// assert_eq!(geom.simplify_with(20.0, SimplifyMode::Outward), 2);
+    /// assert!(geom.area_m2() > area);
+    /// ```
+    pub fn simplify_with(&mut self, max_error: f64, mode: SimplifyMode) -> usize {
+        let thresholds = self.removal_thresholds(mode);
+        let Self::Polygon { segments } = self else {
+            return 0;
+        };
+        let len = segments.len();
+        let mut thresholds = thresholds.into_iter();
+        segments.retain(|_| thresholds.next().is_some_and(|t| t > max_error));
+        len - segments.len()
+    }
+
+    /// The largest maximum error at which each polygon segment is kept by
+    /// [`Self::simplify_with`]. Segments that are always kept get infinity.
+    fn removal_thresholds(&self, mode: SimplifyMode) -> Vec<f64> {
+        let Self::Polygon { segments } = self else {
+            return vec![];
+        };
+        let mut thresholds = vec![f64::INFINITY; segments.len()];
+        // Sign of the cross product for points outside of an edge
+        let outside = match (mode, self.orientation()) {
+            (SimplifyMode::Any, _) => None,
+            (SimplifyMode::Outward, Some(Orientation::Clockwise)) => Some(1.0),
+            (SimplifyMode::Outward, Some(Orientation::Counterclockwise)) => Some(-1.0),
+            (SimplifyMode::Outward, None) => return thresholds,
+        };
+
+        // Runs of points are simplified between anchors that are always kept
+        let mut anchors: Vec<usize> = (0..segments.len())
+            .filter(|&i| {
+                i == 0
+                    || i == segments.len() - 1
+                    || !matches!(segments[i], PolygonSegment::Point(_))
+            })
+            .collect();
+        if anchors.len() <= 2 {
+            anchors.extend(triangle(segments));
+            anchors.sort_unstable();
+            anchors.dedup();
+        }
+
+        for pair in anchors.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let points = segments[from + 1..to]
+                .iter()
+                .map(|segment| segment.start_point())
+                .collect::<Vec<_>>();
+            let run_thresholds = douglas_peucker(
+                &segments[from].end_point(),
+                &segments[to].start_point(),
+                &points,
+                outside,
+            );
+            thresholds[from + 1..to].copy_from_slice(&run_thresholds);
+        }
+        thresholds
+    }
+}
+
+/// Simplify the airspaces with the smallest maximum error that results in
+/// at most `max_points` segments in total, and return that maximum error.
+///
+/// Every polygon segment counts as one point, and every circle as one.
+/// Returns an error if the segments that are always kept (see
+/// [`Geometry::simplify`]) exceed the budget.
+pub fn simplify_to_budget(
+    airspaces: &mut [Airspace],
+    max_points: usize,
+    mode: SimplifyMode,
+) -> Result<f64, String> {
+    let thresholds = airspaces
+        .iter()
+        .map(|airspace| match &airspace.geom {
+            Geometry::Circle { .. } => vec![f64::INFINITY],
+            geom => geom.removal_thresholds(mode),
+        })
+        .collect::<Vec<_>>();
+    let mut removable = thresholds
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|t| t.is_finite())
+        .collect::<Vec<_>>();
+    let total = thresholds.iter().map(Vec::len).sum::<usize>();
+    if total <= max_points {
+        return Ok(0.0);
+    }
+    let fixed = total - removable.len();
+    if fixed > max_points {
+        return Err(format!(
+            "Could not simplify to {max_points} points ({fixed} points are always kept)"
+        ));
+    }
+
+    // Keep the points with the largest thresholds
+    removable.sort_unstable_by(|a, b| b.total_cmp(a));
+    let max_error = removable[max_points - fixed];
+    for airspace in airspaces {
+        airspace.geom.simplify_with(max_error, mode);
+    }
+    Ok(max_error)
+}
+
+/// Indices of three points spanning a polygon without arcs: the first point,
+/// the point farthest from it and the point farthest from the line between
+/// both.
+fn triangle(segments: &[PolygonSegment]) -> Vec<usize> {
+    let Some(first) = segments.first().map(PolygonSegment::start_point) else {
+        return vec![];
+    };
+    let planar = segments
+        .iter()
+        .map(|segment| local(&first, &segment.start_point(), first.lat()))
+        .collect::<Vec<_>>();
+    let farthest = |distance: &dyn Fn(&(f64, f64)) -> f64| {
+        (0..planar.len()).max_by(|&i, &j| distance(&planar[i]).total_cmp(&distance(&planar[j])))
+    };
+    let Some(second) = farthest(&|&(x, y)| x.hypot(y)) else {
+        return vec![];
+    };
+    let (x2, y2) = planar[second];
+    let third = farthest(&|&(x, y)| (x2 * y - y2 * x).abs());
+    [Some(0), Some(second), third]
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Douglas–Peucker simplification of the points between `from` and `to`.
+///
+/// Returns the largest maximum error at which each point is kept. If
+/// `outside` is given, points on that side of an edge (by the sign of the
+/// cross product) are always kept.
+fn douglas_peucker(from: &Coord, to: &Coord, points: &[Coord], outside: Option<f64>) -> Vec<f64> {
+    let mut thresholds = vec![0.0; points.len()];
+    // Ranges of points between two kept points, with the threshold of the
+    // point that split them
+    let mut stack = vec![(0, points.len(), *from, *to, f64::INFINITY)];
+    while let Some((start, end, a, b, parent)) = stack.pop() {
+        let mut split: Option<(usize, f64, bool)> = None;
+        for (i, point) in points.iter().enumerate().take(end).skip(start) {
+            let b_local = local(&a, &b, point.lat());
+            let p = local(&a, point, point.lat());
+            let distance = geometry::segment_distance(p, (0.0, 0.0), b_local);
+            let cross = b_local.0 * p.1 - b_local.1 * p.0;
+            let is_outside = outside.is_some_and(|sign| cross * sign > 0.0);
+            let better = split
+                .is_none_or(|(_, max, max_outside)| (is_outside, distance) > (max_outside, max));
+            if better {
+                split = Some((i, distance, is_outside));
+            }
+        }
+        let Some((i, distance, is_outside)) = split else {
+            continue;
+        };
+        let threshold = if is_outside { f64::INFINITY } else { distance }.min(parent);
+        thresholds[i] = threshold;
+        stack.push((start, i, a, points[i], threshold));
+        stack.push((i + 1, end, points[i], b, threshold));
+    }
+    thresholds
+}
+
+/// Position of the point in metres east and north of `origin`, scaled at
+/// the given latitude. Straight edges in latitude/longitude stay straight.
+fn local(origin: &Coord, point: &Coord, lat: f64) -> (f64, f64) {
+    let radius = geodesy::gaussian_radius(lat);
+    let d_lng = geodesy::normalize_longitude(point.lng() - origin.lng());
+    (
+        d_lng.to_radians() * lat.to_radians().cos() * radius,
+        (point.lat() - origin.lat()).to_radians() * radius,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArcSegment, Direction};
+
+    fn point(lat: f64, lng: f64) -> PolygonSegment {
+        PolygonSegment::Point(Coord::from_degrees(lat, lng))
+    }
+
+    /// A square with a dent of about 11 m inwards and a bump of about 11 m
+    /// outwards on its northern edge.
+    fn square() -> Geometry {
+        Geometry::Polygon {
+            segments: vec![
+                point(47.0, 8.0),
+                point(47.0, 8.2),
+                point(46.9999, 8.4),
+                point(47.0, 8.6),
+                point(47.0001, 8.8),
+                point(47.0, 9.0),
+                point(46.0, 9.0),
+                point(46.0, 8.0),
+                point(47.0, 8.0),
+            ],
+        }
+    }
+
+    #[test]
+    fn simplify() {
+        let mut geom = square();
+        // Only the point between the dent and the bump lies on the edge
+        assert_eq!(geom.simplify(1.0), 1);
+        assert_eq!(geom.simplify(20.0), 3);
+        assert_eq!(
+            geom,
+            Geometry::Polygon {
+                segments: vec![
+                    point(47.0, 8.0),
+                    point(47.0, 9.0),
+                    point(46.0, 9.0),
+                    point(46.0, 8.0),
+                    point(47.0, 8.0),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn outward() {
+        for reversed in [false, true] {
+            let mut geom = square();
+            if reversed {
+                let Geometry::Polygon { segments } = &mut geom else {
+                    unreachable!();
+                };
+                segments.reverse();
+            }
+            let area = geom.area_m2();
+            assert_eq!(geom.simplify_with(20.0, SimplifyMode::Outward), 3);
+            let Geometry::Polygon { segments } = &geom else {
+                unreachable!();
+            };
+            // The bump is kept, the dent removed
+            assert!(segments.contains(&point(47.0001, 8.8)));
+            assert!(!segments.contains(&point(46.9999, 8.4)));
+            assert!(geom.area_m2().abs() > area.abs());
+        }
+    }
+
+    #[test]
+    fn keep_arcs_and_area() {
+        let center = Coord::from_degrees(47.0, 8.0);
+        let arc = || {
+            PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 5.0,
+                angle_start: 0.0,
+                angle_end: 90.0,
+                direction: Direction::Cw,
+            })
+        };
+        let mut geom = Geometry::Polygon {
+            segments: vec![
+                point(47.0, 8.0),
+                arc(),
+                point(46.99, 8.05),
+                point(46.98, 8.04),
+                point(47.0, 8.0),
+            ],
+        };
+        assert_eq!(geom.simplify(10_000.0), 2);
+        assert_eq!(
+            geom,
+            Geometry::Polygon {
+                segments: vec![point(47.0, 8.0), arc(), point(47.0, 8.0)],
+            }
+        );
+
+        // A triangle is kept at any error
+        let mut geom = Geometry::Polygon {
+            segments: vec![
+                point(47.0, 8.0),
+                point(47.0, 8.5),
+                point(47.0, 9.0),
+                point(46.5, 9.0),
+                point(46.0, 9.0),
+                point(46.5, 8.5),
+                point(47.0, 8.0),
+            ],
+        };
+        assert_eq!(geom.simplify(f64::MAX), 3);
+        assert!(geom.area_m2() > 0.0);
+    }
+
+    #[test]
+    fn budget() {
+        let mut airspaces = [square(), square()]
+            .into_iter()
+            .map(|geom| Airspace {
+                name: "Square".to_string(),
+                class: crate::Class::D,
+                type_: None,
+                lower_bound: crate::Altitude::Gnd,
+                upper_bound: crate::Altitude::FlightLevel(100),
+                geom,
+                frequency: None,
+                call_sign: None,
+                transponder_code: None,
+                activation_times: None,
+                label_points: vec![],
+                extensions: vec![],
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            simplify_to_budget(&mut airspaces, 18, SimplifyMode::Any),
+            Ok(0.0)
+        );
+        let max_error = simplify_to_budget(&mut airspaces, 12, SimplifyMode::Any).unwrap();
+        assert!(max_error > 5.0 && max_error < 20.0, "{max_error}");
+        let points = airspaces
+            .iter()
+            .map(|airspace| match &airspace.geom {
+                Geometry::Polygon { segments } => segments.len(),
+                Geometry::Circle { .. } => 1,
+            })
+            .sum::<usize>();
+        assert!(points <= 12, "{points}");
+        assert!(simplify_to_budget(&mut airspaces, 7, SimplifyMode::Any).is_err());
+    }
+}
@@ -0,0 +1,370 @@
+use std::{collections::BTreeSet, fmt};
+
+use crate::{Coord, Geometry, PolygonSegment, geodesy, geometry};
+
+/// Tolerance in metres for flattening arcs when checking for intersections.
+const ARC_TOLERANCE: f64 = 1.0;
+
+/// A problem found by [`Geometry::validate`].
+///
+/// Segment indices refer to [`Geometry::Polygon::segments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum GeometryIssue {
+    /// The boundary of a segment crosses or touches the boundary of another
+    /// (or the same) segment. The boundary of a segment includes the straight
+    /// edge to the next segment. `segment` is never larger than `other`.
+    SelfIntersection { segment: usize, other: usize },
+    /// The radius of an arc, or of the circle if `segment` is `None`, is not
+    /// positive
+    InvalidRadius { segment: Option<usize> },
+    /// An arc has identical start and end points (or angles)
+    IdenticalArcEndpoints { segment: usize },
+    /// The polygon has fewer than three distinct points (including points
+    /// along its arcs)
+    TooFewPoints { distinct: usize },
+}
+
+impl fmt::Display for GeometryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SelfIntersection { segment, other } if segment == other => {
+                write!(f, "Segment {segment} intersects itself")
+            }
+            Self::SelfIntersection { segment, other } => {
+                write!(f, "Segment {segment} intersects segment {other}")
+            }
+            Self::InvalidRadius { segment: None } => write!(f, "Circle radius is not positive"),
+            Self::InvalidRadius {
+                segment: Some(segment),
+            } => write!(f, "Arc radius of segment {segment} is not positive"),
+            Self::IdenticalArcEndpoints { segment } => {
+                write!(
+                    f,
+                    "Arc of segment {segment} has identical start and end points"
+                )
+            }
+            Self::TooFewPoints { distinct } => {
+                write!(f, "Polygon has only {distinct} distinct points")
+            }
+        }
+    }
+}
+
+impl Geometry {
+    /// Check the geometry for self-intersections and degenerate shapes.
+    ///
+    /// Returns an empty list for valid geometries. Arcs are flattened (see
+    /// [`Self::to_points`]) to find intersections between arcs and other
+    /// edges. Edges that only touch at the point they share are not reported.
+    /// Polygons across the antimeridian are supported, polygons around a pole
+    /// are only checked away from their first point.
+    pub fn validate(&self) -> Vec<GeometryIssue> {
+        let segments = match self {
+            Self::Circle { radius, .. } if *radius > 0.0 => return vec![],
+            Self::Circle { .. } => return vec![GeometryIssue::InvalidRadius { segment: None }],
+            Self::Polygon { segments } => segments,
+        };
+
+        let mut issues = Vec::new();
+        for (index, segment) in segments.iter().enumerate() {
+            let (invalid_radius, identical_endpoints) = match segment {
+                PolygonSegment::Point(_) => continue,
+                PolygonSegment::ArcSegment(arc) => (
+                    arc.radius <= 0.0 || arc.radius.is_nan(),
+                    (arc.angle_end - arc.angle_start).rem_euclid(360.0) == 0.0,
+                ),
+                PolygonSegment::Arc(arc) => (
+                    arc.start == arc.centerpoint || arc.end == arc.centerpoint,
+                    arc.start == arc.end,
+                ),
+            };
+            if invalid_radius {
+                issues.push(GeometryIssue::InvalidRadius {
+                    segment: Some(index),
+                });
+            }
+            if identical_endpoints {
+                issues.push(GeometryIssue::IdenticalArcEndpoints { segment: index });
+            }
+        }
+
+        let points = geometry::boundary(segments, ARC_TOLERANCE);
+        let distinct = points
+            .iter()
+            .map(|(point, _)| point)
+            .collect::<BTreeSet<_>>()
+            .len();
+        if distinct < 3 {
+            issues.push(GeometryIssue::TooFewPoints { distinct });
+            return issues;
+        }
+
+        issues.extend(
+            intersecting_segments(&points)
+                .into_iter()
+                .map(|(segment, other)| GeometryIssue::SelfIntersection { segment, other }),
+        );
+        issues
+    }
+}
+
+/// An edge of the flattened ring in latitude/longitude.
+struct Edge {
+    from: (f64, f64),
+    to: (f64, f64),
+    segment: usize,
+}
+
+impl Edge {
+    fn min_lng(&self) -> f64 {
+        self.from.0.min(self.to.0)
+    }
+
+    fn max_lng(&self) -> f64 {
+        self.from.0.max(self.to.0)
+    }
+
+    fn intersects(&self, other: &Edge) -> bool {
+        let (p1, p2, q1, q2) = (self.from, self.to, other.from, other.to);
+        let d1 = orientation(q1, q2, p1);
+        let d2 = orientation(q1, q2, p2);
+        let d3 = orientation(p1, p2, q1);
+        let d4 = orientation(p1, p2, q2);
+        if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+            return true;
+        }
+        (d1 == 0.0 && on_edge(q1, q2, p1))
+            || (d2 == 0.0 && on_edge(q1, q2, p2))
+            || (d3 == 0.0 && on_edge(p1, p2, q1))
+            || (d4 == 0.0 && on_edge(p1, p2, q2))
+    }
+}
+
+/// Pairs of segment indices whose edges intersect, for an implicitly closed
+/// ring of points tagged with their segment index.
+fn intersecting_segments(points: &[(Coord, usize)]) -> BTreeSet<(usize, usize)> {
+    // Unwrap longitudes, so that edges across the antimeridian stay short
+    let mut lng = points.first().map_or(0.0, |(point, _)| point.lng());
+    let mut edges = Vec::with_capacity(points.len());
+    for (i, (from, segment)) in points.iter().enumerate() {
+        let (to, _) = &points[(i + 1) % points.len()];
+        let d_lng = geodesy::normalize_longitude(to.lng() - from.lng());
+        if from != to {
+            edges.push(Edge {
+                from: (lng, from.lat()),
+                to: (lng + d_lng, to.lat()),
+                segment: *segment,
+            });
+        }
+        lng += d_lng;
+    }
+    let adjacent = |i: usize, j: usize| {
+        let diff = i.abs_diff(j);
+        diff == 1 || diff == edges.len() - 1
+    };
+
+    // Sweep along the longitude, only comparing edges with overlapping
+    // longitude ranges
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|&i, &j| edges[i].min_lng().total_cmp(&edges[j].min_lng()));
+    let mut pairs = BTreeSet::new();
+    for (k, &i) in order.iter().enumerate() {
+        for &j in &order[k + 1..] {
+            if edges[j].min_lng() > edges[i].max_lng() {
+                break;
+            }
+            if !adjacent(i, j) && edges[i].intersects(&edges[j]) {
+                let (a, b) = (edges[i].segment, edges[j].segment);
+                pairs.insert((a.min(b), a.max(b)));
+            }
+        }
+    }
+    pairs
+}
+
+/// Cross product (b - a) × (c - a).
+fn orientation(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Returns true if `p`, known to be collinear with the edge, lies on it.
+fn on_edge(a: (f64, f64), b: (f64, f64), p: (f64, f64)) -> bool {
+    p.0 >= a.0.min(b.0) && p.0 <= a.0.max(b.0) && p.1 >= a.1.min(b.1) && p.1 <= a.1.max(b.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Arc, ArcSegment, Direction};
+
+    fn point(lat: f64, lng: f64) -> PolygonSegment {
+        PolygonSegment::Point(Coord::from_degrees(lat, lng))
+    }
+
+    fn validate(segments: Vec<PolygonSegment>) -> Vec<GeometryIssue> {
+        Geometry::Polygon { segments }.validate()
+    }
+
+    #[test]
+    fn valid() {
+        let square = vec![
+            point(47.0, 8.0),
+            point(47.0, 9.0),
+            point(46.0, 9.0),
+            point(46.0, 8.0),
+            point(47.0, 8.0),
+        ];
+        assert_eq!(validate(square), vec![]);
+
+        let center = Coord::from_degrees(47.0, 8.0);
+        let pie = vec![
+            PolygonSegment::Point(center),
+            PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 5.0,
+                angle_start: 0.0,
+                angle_end: 270.0,
+                direction: Direction::Cw,
+            }),
+        ];
+        assert_eq!(validate(pie), vec![]);
+
+        let circle = Geometry::Circle {
+            centerpoint: center,
+            radius: 5.0,
+        };
+        assert_eq!(circle.validate(), vec![]);
+    }
+
+    #[test]
+    fn bow_tie() {
+        let issues = validate(vec![
+            point(47.0, 8.0),
+            point(47.0, 9.0),
+            point(46.0, 8.0),
+            point(46.0, 9.0),
+        ]);
+        assert_eq!(
+            issues,
+            vec![GeometryIssue::SelfIntersection {
+                segment: 1,
+                other: 3
+            }]
+        );
+        assert_eq!(issues[0].to_string(), "Segment 1 intersects segment 3");
+    }
+
+    #[test]
+    fn arc_crossing_edge() {
+        // The edge from the end of the arc and the edge from segment 1 both
+        // cut through the arc
+        let center = Coord::from_degrees(47.0, 8.0);
+        let issues = validate(vec![
+            PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 10.0,
+                angle_start: 0.0,
+                angle_end: 180.0,
+                direction: Direction::Cw,
+            }),
+            point(47.0, 8.3),
+            point(47.1, 7.8),
+        ]);
+        assert_eq!(
+            issues,
+            vec![
+                GeometryIssue::SelfIntersection {
+                    segment: 0,
+                    other: 0
+                },
+                GeometryIssue::SelfIntersection {
+                    segment: 0,
+                    other: 1
+                },
+            ]
+        );
+        assert_eq!(issues[0].to_string(), "Segment 0 intersects itself");
+    }
+
+    #[test]
+    fn degenerate() {
+        let circle = Geometry::Circle {
+            centerpoint: Coord::from_degrees(47.0, 8.0),
+            radius: 0.0,
+        };
+        assert_eq!(
+            circle.validate(),
+            vec![GeometryIssue::InvalidRadius { segment: None }]
+        );
+
+        let center = Coord::from_degrees(47.0, 8.0);
+        let issues = validate(vec![
+            point(47.2, 7.8),
+            PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: -1.0,
+                angle_start: 10.0,
+                angle_end: 100.0,
+                direction: Direction::Cw,
+            }),
+            PolygonSegment::Arc(Arc {
+                centerpoint: center,
+                start: Coord::from_degrees(46.8, 8.0),
+                end: Coord::from_degrees(46.8, 8.0),
+                direction: Direction::Cw,
+            }),
+            PolygonSegment::ArcSegment(ArcSegment {
+                centerpoint: center,
+                radius: 1.0,
+                angle_start: 90.0,
+                angle_end: -270.0,
+                direction: Direction::Ccw,
+            }),
+        ]);
+        assert_eq!(
+            &issues[..3],
+            [
+                GeometryIssue::InvalidRadius { segment: Some(1) },
+                GeometryIssue::IdenticalArcEndpoints { segment: 2 },
+                GeometryIssue::IdenticalArcEndpoints { segment: 3 },
+            ]
+        );
+
+        let line = vec![point(47.0, 8.0), point(47.0, 9.0), point(47.0, 8.0)];
+        assert_eq!(
+            validate(line),
+            vec![GeometryIssue::TooFewPoints { distinct: 2 }]
+        );
+        assert_eq!(
+            validate(vec![]),
+            vec![GeometryIssue::TooFewPoints { distinct: 0 }]
+        );
+    }
+
+    #[test]
+    fn antimeridian() {
+        let square = vec![
+            point(10.0, 170.0),
+            point(10.0, -170.0),
+            point(-10.0, -170.0),
+            point(-10.0, 170.0),
+        ];
+        assert_eq!(validate(square), vec![]);
+
+        let bow_tie = vec![
+            point(10.0, 170.0),
+            point(10.0, -170.0),
+            point(-10.0, 170.0),
+            point(-10.0, -170.0),
+        ];
+        assert_eq!(
+            validate(bow_tie),
+            vec![GeometryIssue::SelfIntersection {
+                segment: 1,
+                other: 3
+            }]
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use std::io::Write;
+
+use crate::{Airspace, Coord, Direction, RemapTable, record::Record};
+
+/// Line ending used when writing OpenAir files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    #[default]
+    Crlf,
+}
+
+impl LineEnding {
+    /// Returns the characters of this line ending.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+        }
+    }
+}
+
+/// Notation used when writing coordinates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CoordStyle {
+    /// Degrees, minutes and seconds (`47:30:15 N 008:30:20.5 E`).
+    ///
+    /// Fractional seconds are only written if needed, so that no precision
+    /// is lost.
+    #[default]
+    Dms,
+    /// Degrees, minutes and seconds with a fixed number of decimal places
+    /// (at most 3). `DmsDecimal(0)` writes rounded whole seconds.
+    DmsDecimal(u8),
+    /// Degrees and decimal minutes with a fixed number of decimal places (at
+    /// most 5), e.g. `47:30.25 N 008:30.342 E`.
+    Ddm(u8),
+}
+
+/// Spelling of altitudes at ground level.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GroundSpelling {
+    /// `GND`
+    #[default]
+    Gnd,
+    /// `SFC`
+    Sfc,
+}
+
+impl GroundSpelling {
+    /// Returns the OpenAir spelling.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Gnd => "GND",
+            Self::Sfc => "SFC",
+        }
+    }
+}
+
+/// Spelling of the mean sea level reference.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MslSpelling {
+    /// `AMSL`, e.g. `4500ft AMSL`
+    #[default]
+    Amsl,
+    /// `MSL`, e.g. `4500ft MSL`
+    Msl,
+}
+
+impl MslSpelling {
+    /// Returns the OpenAir spelling.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Amsl => "AMSL",
+            Self::Msl => "MSL",
+        }
+    }
+}
+
+/// Spelling of an unlimited upper bound.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnlimitedSpelling {
+    /// `UNLIM`
+    #[default]
+    Unlim,
+    /// `UNL`
+    Unl,
+}
+
+impl UnlimitedSpelling {
+    /// Returns the OpenAir spelling.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unlim => "UNLIM",
+            Self::Unl => "UNL",
+        }
+    }
+}
+
+/// Spelling of restricted, danger and prohibited area types in `AY` records.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TypeSpelling {
+    /// `RESTRICTED`, `DANGER` and `PROHIBITED`
+    #[default]
+    Long,
+    /// `R`, `Q` and `P`, as used by OpenAIP exports
+    Short,
+}
+
+/// Record type used for arcs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ArcStyle {
+    /// Write arcs in the form they were parsed in
+    #[default]
+    Preserve,
+    /// Write all arcs as `DA` records (radius and angles), rounding the values
+    /// of converted `DB` arcs to 0.001 NM and 0.01°
+    Da,
+    /// Write all arcs as `DB` records (start and end points)
+    Db,
+}
+
+/// Separator between the values of `DA` and `DB` records.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ArcSpacing {
+    /// A comma followed by a space (`DA 5, 0, 90`)
+    #[default]
+    Spaced,
+    /// A comma only (`DA 5,0,90`)
+    Compact,
+}
+
+impl ArcSpacing {
+    /// Returns the separator between two values.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Spaced => ", ",
+            Self::Compact => ",",
+        }
+    }
+}
+
+/// What to write between two airspaces.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum Separator {
+    /// An empty line
+    #[default]
+    BlankLine,
+    /// A comment line (`* <text>`)
+    Comment(String),
+    /// Nothing, airspaces directly follow each other
+    Nothing,
+}
+
+/// Options controlling the OpenAir output style.
+///
+/// The default options produce the same output as [`Airspace::write`] and
+/// [`write`]. Options are passed to [`Airspace::write_with`], [`write_with`]
+/// or [`AirspaceWriter::with_options`]. Not every consumer accepts every
+/// spelling, so individual choices can be overridden:
+///
+/// ```
+/// use openair::{CoordStyle, LineEnding, WriterOptions};
+///
+/// let options = WriterOptions {
+///     line_ending: LineEnding::Lf,
+///     coord_style: CoordStyle::DmsDecimal(0),
+///     ..Default::default()
+/// };
+/// # let _ = options;
+/// ```
+///
+/// [`Airspace::write`]: crate::Airspace::write
+/// [`Airspace::write_with`]: crate::Airspace::write_with
+/// [`write`]: crate::write
+/// [`write_with`]: crate::write_with
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WriterOptions {
+    /// Line ending of every record
+    pub line_ending: LineEnding,
+    /// Notation of coordinates in `AT`, `V X=`, `DP` and `DB` records
+    pub coord_style: CoordStyle,
+    /// Spelling of [`Altitude::Gnd`](crate::Altitude::Gnd)
+    pub ground: GroundSpelling,
+    /// Spelling of the reference of [`Altitude::FeetAmsl`](crate::Altitude::FeetAmsl)
+    pub msl: MslSpelling,
+    /// Spelling of [`Altitude::Unlimited`](crate::Altitude::Unlimited)
+    pub unlimited: UnlimitedSpelling,
+    /// Spelling of restricted, danger and prohibited area types
+    pub type_spelling: TypeSpelling,
+    /// Record type of arcs, for devices that only support `DA` or `DB`
+    pub arc_style: ArcStyle,
+    /// Separator between the values of `DA` and `DB` records
+    pub arc_spacing: ArcSpacing,
+    /// Write an `AT` record at [`Geometry::label_point`](crate::Geometry::label_point)
+    /// for airspaces without label placement hints
+    pub compute_label_points: bool,
+    /// Separator between airspaces
+    pub separator: Separator,
+    /// Only write `V X=` and `V D=` records if the value changes within an
+    /// airspace. Readers reset `D` to `+` at the start of every airspace.
+    pub skip_redundant_variables: bool,
+    /// Remap the class and type of every airspace while writing, leaving the
+    /// airspaces themselves unchanged
+    pub remap: Option<RemapTable>,
+}
+
+/// Writes airspaces one at a time, e.g. while streaming them from
+/// [`parse`](crate::parse).
+///
+/// Separators between airspaces are written automatically, a file header
+/// and comments can be added in between:
+///
+/// ```
+/// use openair::AirspaceWriter;
+///
+/// let input = "AC D\nAN Zone\nAL GND\nAH FL100\nV X=47:00:00 N 008:00:00 E\nDC 5\n";
+///
+/// let mut writer = AirspaceWriter::new(Vec::new());
+/// writer.write_header("Converted airspaces\nDo not use for navigation").unwrap();
+/// for airspace in openair::parse(input.as_bytes()) {
+///     writer.write_airspace(&airspace.unwrap()).unwrap();
+/// }
+/// let output = writer.finish().unwrap();
+/// assert!(output.starts_with(b"* Converted airspaces\r\n* Do not use for navigation\r\n\r\nAC D\r\n"));
+/// ```
+#[derive(Debug)]
+pub struct AirspaceWriter<W: Write> {
+    writer: W,
+    options: WriterOptions,
+    /// True if anything has been written yet
+    started: bool,
+    /// True if the last item written was an airspace
+    after_airspace: bool,
+}
+
+impl<W: Write> AirspaceWriter<W> {
+    /// Create a writer with default options.
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, WriterOptions::default())
+    }
+
+    /// Create a writer with the given options.
+    pub fn with_options(writer: W, options: WriterOptions) -> Self {
+        Self {
+            writer,
+            options,
+            started: false,
+            after_airspace: false,
+        }
+    }
+
+    /// Returns the options used by this writer.
+    pub fn options(&self) -> &WriterOptions {
+        &self.options
+    }
+
+    /// Writes a file header as comment lines, followed by a blank line.
+    ///
+    /// Fails if anything has been written before.
+    pub fn write_header(&mut self, header: &str) -> std::io::Result<()> {
+        if self.started {
+            return Err(std::io::Error::other(
+                "Header must be written before any other content",
+            ));
+        }
+        self.write_comment_lines(header)?;
+        write!(self.writer, "{}", self.options.line_ending.as_str())?;
+        self.started = true;
+        Ok(())
+    }
+
+    /// Writes a comment. Every line of the text becomes a comment line.
+    ///
+    /// A comment following an airspace is preceded by the separator.
+    pub fn write_comment(&mut self, comment: &str) -> std::io::Result<()> {
+        self.write_separator()?;
+        self.write_comment_lines(comment)?;
+        self.started = true;
+        self.after_airspace = false;
+        Ok(())
+    }
+
+    /// Writes an airspace, preceded by the separator if the previous item
+    /// was an airspace as well.
+    pub fn write_airspace(&mut self, airspace: &Airspace) -> std::io::Result<()> {
+        self.write_separator()?;
+        airspace.write_with(&mut self.writer, &self.options)?;
+        self.started = true;
+        self.after_airspace = true;
+        Ok(())
+    }
+
+    /// Flushes the output and returns the underlying writer.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_separator(&mut self) -> std::io::Result<()> {
+        if !self.after_airspace {
+            return Ok(());
+        }
+        let eol = self.options.line_ending.as_str();
+        match self.options.separator {
+            Separator::BlankLine => write!(self.writer, "{eol}"),
+            Separator::Comment(ref text) if text.is_empty() => write!(self.writer, "*{eol}"),
+            Separator::Comment(ref text) => write!(self.writer, "* {text}{eol}"),
+            Separator::Nothing => Ok(()),
+        }
+    }
+
+    fn write_comment_lines(&mut self, text: &str) -> std::io::Result<()> {
+        let eol = self.options.line_ending.as_str();
+        if text.is_empty() {
+            return write!(self.writer, "*{eol}");
+        }
+        for line in text.lines() {
+            match line.trim_end() {
+                "" => write!(self.writer, "*{eol}")?,
+                line => write!(self.writer, "* {line}{eol}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Tracks the `V X=` and `V D=` variables while writing a single airspace.
+#[derive(Debug, Default)]
+pub(crate) struct Variables {
+    x: Option<Coord>,
+    d: Direction,
+}
+
+impl Variables {
+    /// Writes a `V X=` record, unless it is redundant and the options allow
+    /// skipping it.
+    pub(crate) fn write_x<W: Write>(
+        &mut self,
+        writer: W,
+        x: Coord,
+        options: &WriterOptions,
+    ) -> std::io::Result<()> {
+        if options.skip_redundant_variables && self.x == Some(x) {
+            return Ok(());
+        }
+        self.x = Some(x);
+        Record::VarX(x).write(writer, options)
+    }
+
+    /// Writes a `V D=` record, unless it is redundant and the options allow
+    /// skipping it.
+    pub(crate) fn write_d<W: Write>(
+        &mut self,
+        writer: W,
+        d: Direction,
+        options: &WriterOptions,
+    ) -> std::io::Result<()> {
+        if options.skip_redundant_variables && self.d == d {
+            return Ok(());
+        }
+        self.d = d;
+        Record::VarD(d).write(writer, options)
+    }
+}
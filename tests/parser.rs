@@ -136,20 +136,14 @@ fn multi_variable() {
         Geometry::Polygon {
             segments: vec![
                 PolygonSegment::ArcSegment(ArcSegment {
-                    centerpoint: Coord {
-                        lat: 52.0,
-                        lng: 13.0
-                    },
+                    centerpoint: Coord::from_degrees(52.0, 13.0),
                     radius: 2.0,
                     angle_start: 0.0,
                     angle_end: 30.0,
                     direction: Direction::Cw,
                 }),
                 PolygonSegment::ArcSegment(ArcSegment {
-                    centerpoint: Coord {
-                        lat: 52.0,
-                        lng: 13.0
-                    },
+                    centerpoint: Coord::from_degrees(52.0, 13.0),
                     radius: 4.0,
                     angle_start: 60.0,
                     angle_end: 30.0,
@@ -179,10 +173,39 @@ fn extension_records() {
     .as_bytes();
     let spaces = parse(&mut a).collect::<Result<Vec<_>, _>>().unwrap();
     let airspace = spaces.last().unwrap();
-    assert_eq!(airspace.type_, Some("AWY".to_string()));
-    assert_eq!(airspace.frequency, Some("132.350".to_string()));
+    assert_eq!(airspace.type_, Some(AirspaceType::Awy));
+    assert_eq!(
+        airspace.frequency,
+        Some(Frequency::parse("132.350").unwrap())
+    );
     assert_eq!(airspace.call_sign, Some("Dutch Mil".to_string()));
-    assert_eq!(airspace.transponder_code, Some(1234));
+    assert_eq!(airspace.transponder_code, Some(Squawk::new(1234).unwrap()));
+    assert_eq!(
+        airspace.extensions,
+        vec![("A*".to_string(), "custom extension".to_string())]
+    );
+}
+
+/// Test that unparsable AT records are skipped.
+#[test]
+fn invalid_label_point() {
+    let mut a = indoc! {"
+        AC D
+        AN SOMESPACE
+        AL GND
+        AH 100 ft AGL
+        AT foo
+        AT 52:00:00 N 013:00:00 E
+        V X=52:00:00 N 013:00:00 E
+        DC 5
+    "}
+    .as_bytes();
+    let spaces = parse(&mut a).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(spaces.len(), 1);
+    assert_eq!(
+        spaces[0].label_points,
+        vec![Coord::from_degrees(52.0, 13.0)]
+    );
 }
 
 /// Test that AN (Airspace Name) can act as a separator when it appears before AC.
@@ -237,3 +260,204 @@ fn an_record_as_separator() {
     assert_eq!(second.lower_bound, Altitude::FeetAmsl(1000));
     assert_eq!(second.upper_bound, Altitude::FlightLevel(100));
 }
+
+#[test]
+fn switzerland_bbox() {
+    let airspace = include_bytes!("../example_data/Switzerland.txt");
+    let mut cursor = Cursor::new(airspace);
+
+    let spaces = parse(&mut cursor).collect::<Result<Vec<_>, _>>().unwrap();
+    let bbox = BoundingBox::from_airspaces(&spaces).unwrap();
+
+    // Every airspace lies within the dataset bounding box
+    for space in &spaces {
+        let space_bbox = space.bbox().unwrap();
+        assert_eq!(bbox.union(&space_bbox), bbox);
+        // Flattened points are rounded to milli-arc-seconds
+        let rounding = 0.5 / 3_600_000.0;
+        for point in space.geom.to_points(100.0) {
+            assert!(
+                point.lat() >= space_bbox.min_lat - rounding
+                    && point.lat() <= space_bbox.max_lat + rounding
+                    && point.lng() >= space_bbox.min_lng - rounding
+                    && point.lng() <= space_bbox.max_lng + rounding,
+                "{} {point:?}",
+                space.name
+            );
+        }
+    }
+    assert!(bbox.min_lat > 45.0 && bbox.max_lat < 48.5, "{bbox:?}");
+    assert!(bbox.min_lng > 5.0 && bbox.max_lng < 11.5, "{bbox:?}");
+}
+
+#[test]
+fn switzerland_validate() {
+    let airspace = include_bytes!("../example_data/Switzerland.txt");
+    let mut cursor = Cursor::new(airspace);
+
+    let spaces = parse(&mut cursor).collect::<Result<Vec<_>, _>>().unwrap();
+    let invalid = spaces
+        .iter()
+        .filter_map(|space| {
+            let issues = space.geom.validate();
+            (!issues.is_empty()).then(|| {
+                let issues = issues.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+                format!("{}: {}", space.name, issues.join(", "))
+            })
+        })
+        .collect::<Vec<_>>();
+    assert_debug_snapshot!(invalid);
+}
+
+#[test]
+fn france_fit_arcs() {
+    let airspace = include_bytes!("../example_data/France.txt");
+    let mut cursor = Cursor::new(airspace);
+
+    let mut spaces = parse(&mut cursor)
+        .take(50)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let segments = |spaces: &[Airspace]| {
+        spaces
+            .iter()
+            .map(|space| match &space.geom {
+                Geometry::Circle { .. } => 1,
+                Geometry::Polygon { segments } => segments.len(),
+            })
+            .sum::<usize>()
+    };
+    let before = segments(&spaces);
+
+    // Points are rounded to whole seconds
+    let mut removed = 0;
+    for space in &mut spaces {
+        let area = space.geom.area_m2();
+        removed += space.geom.fit_arcs(20.0);
+        let diff = (space.geom.area_m2() - area).abs() / area;
+        assert!(diff < 0.05, "{}: area differs by {diff}", space.name);
+    }
+    assert_eq!(segments(&spaces), before - removed);
+    assert!(removed > before / 2, "{removed} of {before} removed");
+
+    // The fitted arcs survive a roundtrip
+    let mut buf = Vec::new();
+    write(&mut buf, &spaces).unwrap();
+    let reparsed = parse(&mut buf.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(segments(&reparsed), segments(&spaces));
+}
+
+#[test]
+fn switzerland_simplify_to_budget() {
+    let airspace = include_bytes!("../example_data/Switzerland.txt");
+    let mut cursor = Cursor::new(airspace);
+
+    let mut spaces = parse(&mut cursor).collect::<Result<Vec<_>, _>>().unwrap();
+    let segments = |spaces: &[Airspace]| {
+        spaces
+            .iter()
+            .map(|space| match &space.geom {
+                Geometry::Circle { .. } => 1,
+                Geometry::Polygon { segments } => segments.len(),
+            })
+            .sum::<usize>()
+    };
+    let areas = spaces
+        .iter()
+        .map(|space| space.geom.area_m2().abs())
+        .collect::<Vec<_>>();
+    let budget = segments(&spaces) * 3 / 4;
+
+    let max_error = simplify_to_budget(&mut spaces, budget, SimplifyMode::Outward).unwrap();
+    assert!(max_error > 0.0);
+    assert!(segments(&spaces) <= budget);
+    // Outward simplification never cuts off parts of an airspace
+    for (space, area) in spaces.iter().zip(areas) {
+        assert!(
+            space.geom.area_m2().abs() >= area * (1.0 - 1e-9),
+            "{} shrank",
+            space.name
+        );
+    }
+}
+
+#[test]
+fn switzerland_boolean_operations() {
+    let airspace = include_bytes!("../example_data/Switzerland.txt");
+    let mut cursor = Cursor::new(airspace);
+
+    let spaces = parse(&mut cursor).collect::<Result<Vec<_>, _>>().unwrap();
+    let area = |parts: &[Geometry]| parts.iter().map(Geometry::area_m2).sum::<f64>();
+    let mut pairs = 0;
+    for (i, a) in spaces.iter().enumerate().take(60) {
+        for b in &spaces[i + 1..60] {
+            let (Some(bbox_a), Some(bbox_b)) = (a.geom.bbox(), b.geom.bbox()) else {
+                continue;
+            };
+            if !bbox_a.intersects(&bbox_b) {
+                continue;
+            }
+            pairs += 1;
+            let (area_a, area_b) = (a.geom.area_m2().abs(), b.geom.area_m2().abs());
+            let intersection = area(&a.geom.intersection(&b.geom));
+            let union = area(&a.geom.union(&b.geom));
+            let difference = area(&a.geom.difference(&b.geom));
+            // Areas of the flattened and restored geometries differ slightly
+            let tolerance = (area_a + area_b) * 1e-5;
+            assert!(
+                (union + intersection - area_a - area_b).abs() <= tolerance,
+                "{} and {}",
+                a.name,
+                b.name
+            );
+            assert!(
+                (difference + intersection - area_a).abs() <= tolerance,
+                "{} and {}",
+                a.name,
+                b.name
+            );
+        }
+    }
+    assert!(pairs > 0);
+}
+
+#[test]
+fn germany_border_conflicts() {
+    let parse_file = |data: &[u8]| {
+        parse(&mut Cursor::new(data))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    };
+    let mut spaces = parse_file(include_bytes!("../example_data/Germany.txt"));
+    let count = spaces.len();
+    spaces.extend(parse_file(include_bytes!(
+        "../example_data/Germany_Border.txt"
+    )));
+
+    // Only report conflicts between the two files
+    let report = find_conflicts(&spaces, 10_000.0);
+    let overlaps = report
+        .overlaps
+        .iter()
+        .filter(|overlap| overlap.first < count && overlap.second >= count)
+        .map(|overlap| {
+            format!(
+                "{} / {}: {:?} {:.1} km²",
+                spaces[overlap.first],
+                spaces[overlap.second],
+                overlap.vertical,
+                overlap.area_m2 / 1e6
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_debug_snapshot!(overlaps);
+
+    let duplicates = report
+        .duplicates
+        .iter()
+        .map(|duplicate| format!("{} / {}", spaces[duplicate.first], spaces[duplicate.second]))
+        .collect::<Vec<_>>();
+    assert_debug_snapshot!(duplicates);
+}
@@ -12,25 +12,22 @@ fn serialize_json() {
         upper_bound: Altitude::FeetAgl(3000),
         geom: Geometry::Polygon {
             segments: vec![
-                PolygonSegment::Point(Coord { lat: 1.0, lng: 2.0 }),
-                PolygonSegment::Point(Coord { lat: 1.1, lng: 2.0 }),
+                PolygonSegment::Point(Coord::from_degrees(1.0, 2.0)),
+                PolygonSegment::Point(Coord::from_degrees(1.1, 2.0)),
                 PolygonSegment::Arc(Arc {
-                    centerpoint: Coord {
-                        lat: 1.05,
-                        lng: 2.05,
-                    },
-                    start: Coord { lat: 1.1, lng: 2.0 },
-                    end: Coord { lat: 1.0, lng: 2.1 },
+                    centerpoint: Coord::from_degrees(1.05, 2.05),
+                    start: Coord::from_degrees(1.1, 2.0),
+                    end: Coord::from_degrees(1.0, 2.1),
                     direction: Direction::Cw,
                 }),
                 PolygonSegment::ArcSegment(ArcSegment {
-                    centerpoint: Coord { lat: 3.0, lng: 3.0 },
+                    centerpoint: Coord::from_degrees(3.0, 3.0),
                     radius: 1.5,
                     angle_start: 30.0,
                     angle_end: 45.0,
                     direction: Direction::Ccw,
                 }),
-                PolygonSegment::Point(Coord { lat: 1.0, lng: 2.0 }),
+                PolygonSegment::Point(Coord::from_degrees(1.0, 2.0)),
             ],
         },
         type_: None,
@@ -38,6 +35,8 @@ fn serialize_json() {
         call_sign: None,
         transponder_code: None,
         activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
     };
     assert_json_snapshot!(airspace);
 }
@@ -55,6 +54,46 @@ fn serialize_json_ctr() {
         call_sign: None,
         transponder_code: None,
         activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
+    };
+    assert_json_snapshot!(airspace);
+}
+
+#[test]
+fn serialize_json_type() {
+    let airspace = Airspace {
+        name: "Gliding Sector".into(),
+        class: Class::WaveWindow,
+        lower_bound: Altitude::FlightLevel(100),
+        upper_bound: Altitude::FlightLevel(195),
+        geom: Geometry::Polygon { segments: vec![] },
+        type_: Some(AirspaceType::GlidingSector),
+        frequency: None,
+        call_sign: None,
+        transponder_code: None,
+        activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
+    };
+    assert_json_snapshot!(airspace);
+}
+
+#[test]
+fn serialize_json_extensions() {
+    let airspace = Airspace {
+        name: "Vendor Zone".into(),
+        class: Class::D,
+        lower_bound: Altitude::Gnd,
+        upper_bound: Altitude::FlightLevel(65),
+        geom: Geometry::Polygon { segments: vec![] },
+        type_: None,
+        frequency: None,
+        call_sign: None,
+        transponder_code: None,
+        activation_times: None,
+        label_points: vec![],
+        extensions: vec![("AZ".into(), "vendor data".into())],
     };
     assert_json_snapshot!(airspace);
 }
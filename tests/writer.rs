@@ -1,4 +1,8 @@
-use openair::{Airspace, Altitude, Class, Coord, Geometry, PolygonSegment};
+use openair::{
+    Airspace, AirspaceType, AirspaceWriter, Altitude, ArcSpacing, ArcStyle, Class, Coord,
+    CoordStyle, Frequency, Geometry, GroundSpelling, LineEnding, MslSpelling, PolygonSegment,
+    RemapTable, Separator, TypeSpelling, WriterOptions,
+};
 
 #[test]
 fn write_single_airspace() {
@@ -9,16 +13,15 @@ fn write_single_airspace() {
         lower_bound: Altitude::Gnd,
         upper_bound: Altitude::FlightLevel(100),
         geom: Geometry::Circle {
-            centerpoint: Coord {
-                lat: 47.0,
-                lng: 8.0,
-            },
+            centerpoint: Coord::from_degrees(47.0, 8.0),
             radius: 5.0,
         },
         frequency: None,
         call_sign: None,
         transponder_code: None,
         activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
     };
 
     let mut buf = Vec::new();
@@ -44,16 +47,15 @@ fn write_multiple_airspaces() {
         lower_bound: Altitude::Gnd,
         upper_bound: Altitude::FlightLevel(100),
         geom: Geometry::Circle {
-            centerpoint: Coord {
-                lat: 47.0,
-                lng: 8.0,
-            },
+            centerpoint: Coord::from_degrees(47.0, 8.0),
             radius: 5.0,
         },
         frequency: None,
         call_sign: None,
         transponder_code: None,
         activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
     };
 
     let airspace2 = Airspace {
@@ -64,43 +66,35 @@ fn write_multiple_airspaces() {
         upper_bound: Altitude::FeetAmsl(5000),
         geom: Geometry::Polygon {
             segments: vec![
-                PolygonSegment::Point(Coord {
-                    lat: 46.0,
-                    lng: 7.0,
-                }),
-                PolygonSegment::Point(Coord {
-                    lat: 46.0,
-                    lng: 8.0,
-                }),
-                PolygonSegment::Point(Coord {
-                    lat: 45.0,
-                    lng: 8.0,
-                }),
+                PolygonSegment::Point(Coord::from_degrees(46.0, 7.0)),
+                PolygonSegment::Point(Coord::from_degrees(46.0, 8.0)),
+                PolygonSegment::Point(Coord::from_degrees(45.0, 8.0)),
             ],
         },
         frequency: None,
         call_sign: None,
         transponder_code: None,
         activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
     };
 
     let airspace3 = Airspace {
         name: "Zone C".to_string(),
         class: Class::C,
-        type_: Some("CTR".to_string()),
+        type_: Some(AirspaceType::Ctr),
         lower_bound: Altitude::Gnd,
         upper_bound: Altitude::Unlimited,
         geom: Geometry::Circle {
-            centerpoint: Coord {
-                lat: 48.0,
-                lng: 9.0,
-            },
+            centerpoint: Coord::from_degrees(48.0, 9.0),
             radius: 10.0,
         },
-        frequency: Some("123.45".to_string()),
+        frequency: Some(Frequency::parse("123.45").unwrap()),
         call_sign: Some("TOWER".to_string()),
         transponder_code: None,
         activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
     };
 
     let mut buf = Vec::new();
@@ -155,16 +149,15 @@ fn write_with_vec() {
         lower_bound: Altitude::Gnd,
         upper_bound: Altitude::FlightLevel(50),
         geom: Geometry::Circle {
-            centerpoint: Coord {
-                lat: 47.0,
-                lng: 8.0,
-            },
+            centerpoint: Coord::from_degrees(47.0, 8.0),
             radius: 3.0,
         },
         frequency: None,
         call_sign: None,
         transponder_code: None,
         activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
     };
 
     // Test with borrowed Vec
@@ -182,3 +175,446 @@ fn write_with_vec() {
     DC 3
     ");
 }
+
+#[test]
+fn roundtrip_extensions() {
+    let input = "AC D\r\nAN Test\r\nAL GND\r\nAH FL50\r\nAZ vendor data\r\nA* custom\r\nV X=47:00:00 N 008:00:00 E\r\nDC 3\r\n";
+
+    let airspaces = openair::parse(input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        airspaces[0].extensions,
+        vec![
+            ("AZ".to_string(), "vendor data".to_string()),
+            ("A*".to_string(), "custom".to_string()),
+        ]
+    );
+
+    let mut buf = Vec::new();
+    openair::write(&mut buf, &airspaces).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), input);
+}
+
+#[test]
+fn roundtrip_france_types() {
+    let input = include_str!("../example_data/France.txt");
+    let airspaces = openair::parse(input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let options = WriterOptions {
+        type_spelling: TypeSpelling::Short,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    openair::write_with(&mut buf, &airspaces, &options).unwrap();
+
+    let types = |text: &str| {
+        text.lines()
+            .filter(|line| line.starts_with("AY "))
+            .map(|line| line.trim_end().to_string())
+            .collect::<Vec<_>>()
+    };
+    let expected = types(input);
+    assert!(expected.contains(&"AY R".to_string()));
+    assert_eq!(types(&String::from_utf8(buf).unwrap()), expected);
+}
+
+#[test]
+fn roundtrip_label_points() {
+    // Label placement hints between header records don't start a new airspace
+    let input = "AC D\r\nAN Test\r\nAT 47:00:00 N 008:00:00 E\r\nAL GND\r\nAH FL50\r\nV X=47:00:00 N 008:00:00 E\r\nDC 3\r\n";
+
+    let airspaces = openair::parse(input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(airspaces.len(), 1);
+    assert_eq!(
+        airspaces[0].label_points,
+        vec![Coord::from_degrees(47.0, 8.0)]
+    );
+
+    let mut buf = Vec::new();
+    openair::write(&mut buf, &airspaces).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "AC D\r\nAN Test\r\nAL GND\r\nAH FL50\r\nAT 47:00:00 N 008:00:00 E\r\nV X=47:00:00 N 008:00:00 E\r\nDC 3\r\n"
+    );
+}
+
+#[test]
+fn write_computed_label_points() {
+    let input = "AC D\nAN C-SHAPE\nAL GND\nAH FL50\nDP 47:00:00 N 008:00:00 E\nDP 47:00:00 N 009:00:00 E\n\
+                 DP 46:48:00 N 009:00:00 E\nDP 46:48:00 N 008:12:00 E\nDP 46:12:00 N 008:12:00 E\n\
+                 DP 46:12:00 N 009:00:00 E\nDP 46:00:00 N 009:00:00 E\nDP 46:00:00 N 008:00:00 E\n\
+                 AC D\nAN LABELLED\nAL GND\nAH FL50\nAT 47:00:00 N 008:00:00 E\nV X=47:00:00 N 008:00:00 E\nDC 3\n";
+    let airspaces = openair::parse(input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let options = WriterOptions {
+        line_ending: LineEnding::Lf,
+        separator: Separator::Nothing,
+        compute_label_points: true,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    openair::write_with(&mut buf, &airspaces, &options).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+
+    insta::assert_snapshot!(output, @r"
+    AC D
+    AN C-SHAPE
+    AL GND
+    AH FL50
+    AT 46:06:10.259 N 008:08:57.891 E
+    DP 47:00:00 N 008:00:00 E
+    DP 47:00:00 N 009:00:00 E
+    DP 46:48:00 N 009:00:00 E
+    DP 46:48:00 N 008:12:00 E
+    DP 46:12:00 N 008:12:00 E
+    DP 46:12:00 N 009:00:00 E
+    DP 46:00:00 N 009:00:00 E
+    DP 46:00:00 N 008:00:00 E
+    AC D
+    AN LABELLED
+    AL GND
+    AH FL50
+    AT 47:00:00 N 008:00:00 E
+    V X=47:00:00 N 008:00:00 E
+    DC 3
+    ");
+}
+
+#[test]
+fn write_with_options() {
+    let zone = |name: &str, lower_bound| Airspace {
+        name: name.to_string(),
+        class: Class::D,
+        type_: None,
+        lower_bound,
+        upper_bound: Altitude::FeetAmsl(4500),
+        geom: Geometry::Polygon {
+            segments: vec![
+                PolygonSegment::Point(Coord::parse("47:00:00.4 N 008:00:00 E").unwrap()),
+                PolygonSegment::Point(Coord::parse("47:30:00 N 008:30:00 E").unwrap()),
+            ],
+        },
+        frequency: None,
+        call_sign: None,
+        transponder_code: None,
+        activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
+    };
+    let airspaces = [
+        zone("A", Altitude::Gnd),
+        zone("B", Altitude::FeetAmsl(2000)),
+    ];
+
+    let options = WriterOptions {
+        line_ending: LineEnding::Lf,
+        coord_style: CoordStyle::Ddm(2),
+        ground: GroundSpelling::Sfc,
+        msl: MslSpelling::Msl,
+        separator: Separator::Comment("----".to_string()),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    openair::write_with(&mut buf, &airspaces, &options).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+
+    assert_eq!(
+        output,
+        "AC D\nAN A\nAL SFC\nAH 4500ft MSL\nDP 47:00.01 N 008:00.00 E\nDP 47:30.00 N 008:30.00 E\n\
+         * ----\n\
+         AC D\nAN B\nAL 2000ft MSL\nAH 4500ft MSL\nDP 47:00.01 N 008:00.00 E\nDP 47:30.00 N 008:30.00 E\n"
+    );
+
+    // Without separator, airspaces directly follow each other
+    let options = WriterOptions {
+        separator: Separator::Nothing,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    openair::write_with(&mut buf, &airspaces, &options).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("DP 47:30:00 N 008:30:00 E\r\nAC D\r\nAN B\r\n"));
+}
+
+#[test]
+fn write_remapped() {
+    let input =
+        "AC UNC\nAY OFR\nAN Nest\nAL GND\nAH 2000ft AGL\nV X=47:00:00 N 008:00:00 E\nDC 1\n";
+    let airspaces = openair::parse(&mut input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let options = WriterOptions {
+        line_ending: LineEnding::Lf,
+        remap: Some(RemapTable::parse("class=UNC type=OFR -> class=R type=-").unwrap()),
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    openair::write_with(&mut buf, &airspaces, &options).unwrap();
+
+    insta::assert_snapshot!(String::from_utf8(buf).unwrap(), @r"
+    AC R
+    AN Nest
+    AL GND
+    AH 2000ft AGL
+    V X=47:00:00 N 008:00:00 E
+    DC 1
+    ");
+    assert_eq!(airspaces[0].class, Class::Unclassified);
+}
+
+#[test]
+fn write_arc_spacing() {
+    let input = "AC D\nAN ARCS\nAL GND\nAH 3000 ft\nV X=47:00:00 N 008:00:00 E\nDA 5,0,90\n\
+                 V D=-\nDB 46:55:00 N 008:00:00 E, 47:00:00 N 007:52:40 E\n";
+    let airspaces = openair::parse(&mut input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let options = WriterOptions {
+        line_ending: LineEnding::Lf,
+        arc_spacing: ArcSpacing::Compact,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    openair::write_with(&mut buf, &airspaces, &options).unwrap();
+
+    insta::assert_snapshot!(String::from_utf8(buf).unwrap(), @r"
+    AC D
+    AN ARCS
+    AL GND
+    AH 3000ft AMSL
+    V X=47:00:00 N 008:00:00 E
+    V D=+
+    DA 5,0,90
+    V X=47:00:00 N 008:00:00 E
+    V D=-
+    DB 46:55:00 N 008:00:00 E,47:00:00 N 007:52:40 E
+    ");
+}
+
+#[test]
+fn write_arc_style() {
+    let input = "AC D\nAN ARCS\nAL GND\nAH 3000 ft\nV X=47:00:00 N 008:00:00 E\nDA 5,0,90\n\
+                 V D=-\nDB 46:55:00 N 008:00:00 E, 47:00:00 N 007:52:40 E\n";
+    let airspaces = openair::parse(&mut input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let write = |arc_style| {
+        let options = WriterOptions {
+            line_ending: LineEnding::Lf,
+            coord_style: CoordStyle::DmsDecimal(0),
+            arc_style,
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        openair::write_with(&mut buf, &airspaces, &options).unwrap();
+        String::from_utf8(buf).unwrap()
+    };
+
+    insta::assert_snapshot!(write(ArcStyle::Db), @r"
+    AC D
+    AN ARCS
+    AL GND
+    AH 3000ft AMSL
+    V X=47:00:00 N 008:00:00 E
+    V D=+
+    DB 47:05:00 N 008:00:00 E, 47:00:00 N 008:07:18 E
+    V X=47:00:00 N 008:00:00 E
+    V D=-
+    DB 46:55:00 N 008:00:00 E, 47:00:00 N 007:52:40 E
+    ");
+    insta::assert_snapshot!(write(ArcStyle::Da), @r"
+    AC D
+    AN ARCS
+    AL GND
+    AH 3000ft AMSL
+    V X=47:00:00 N 008:00:00 E
+    V D=+
+    DA 5, 0, 90
+    V X=47:00:00 N 008:00:00 E
+    V D=-
+    DA 5.011, 180, 270.04
+    ");
+    assert!(
+        write(ArcStyle::Preserve).contains("DA 5, 0, 90\nV X=47:00:00 N 008:00:00 E\nV D=-\nDB ")
+    );
+}
+
+#[test]
+fn write_skip_redundant_variables() {
+    let input = "AC C\nAN RENO\nAL 7200 ft\nAH 8400 ft\nV X=39:29.9 N 119:46.1W\nDA 10,270,290\nDA 7,290,320\nDA 10,320,200\nV D=-\nDA 5,200,270\n\n\
+                 AC C\nAN BEALE AFB\nAL 2600ft\nAH 4100 ft\nV X=39:08.2 N 121:26.2 W\nV D=-\nDA 10,9,130\nV D=+\nDA 5,130,9\n";
+    let airspaces = openair::parse(&mut input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let options = WriterOptions {
+        skip_redundant_variables: true,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    openair::write_with(&mut buf, &airspaces, &options).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+
+    insta::assert_snapshot!(output, @r"
+    AC C
+    AN RENO
+    AL 7200ft AMSL
+    AH 8400ft AMSL
+    V X=39:29:54 N 119:46:06 W
+    DA 10, 270, 290
+    DA 7, 290, 320
+    DA 10, 320, 200
+    V D=-
+    DA 5, 200, 270
+
+    AC C
+    AN BEALE AFB
+    AL 2600ft AMSL
+    AH 4100ft AMSL
+    V X=39:08:12 N 121:26:12 W
+    V D=-
+    DA 10, 9, 130
+    V D=+
+    DA 5, 130, 9
+    ");
+
+    // The compact output describes the same airspaces
+    let reparsed = openair::parse(&mut output.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(reparsed, airspaces);
+}
+
+#[test]
+fn airspace_writer_streaming() {
+    let input = "AC D\nAN Zone A\nAL GND\nAH FL100\nV X=47:00:00 N 008:00:00 E\nDC 5\n\n\
+                 AC R\nAN Zone B\nAL GND\nAH FL50\nV X=48:00:00 N 009:00:00 E\nDC 2\n\n\
+                 AC Q\nAN Zone C\nAL GND\nAH FL50\nV X=48:00:00 N 009:00:00 E\nDC 1\n";
+
+    let mut writer = AirspaceWriter::new(Vec::new());
+    writer
+        .write_header("Test airspaces\n\nGenerated file")
+        .unwrap();
+    for (i, airspace) in openair::parse(input.as_bytes()).enumerate() {
+        if i == 1 {
+            writer.write_comment("Restricted areas").unwrap();
+        }
+        writer.write_airspace(&airspace.unwrap()).unwrap();
+    }
+    let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+    insta::assert_snapshot!(output, @r"
+    * Test airspaces
+    *
+    * Generated file
+
+    AC D
+    AN Zone A
+    AL GND
+    AH FL100
+    V X=47:00:00 N 008:00:00 E
+    DC 5
+
+    * Restricted areas
+    AC R
+    AN Zone B
+    AL GND
+    AH FL50
+    V X=48:00:00 N 009:00:00 E
+    DC 2
+
+    AC Q
+    AN Zone C
+    AL GND
+    AH FL50
+    V X=48:00:00 N 009:00:00 E
+    DC 1
+    ");
+
+    // Comments and headers are ignored when reading
+    assert_eq!(openair::parse(output.as_bytes()).count(), 3);
+}
+
+#[test]
+fn airspace_writer_header_after_content() {
+    let mut writer = AirspaceWriter::new(Vec::new());
+    writer.write_comment("First").unwrap();
+    let err = writer.write_header("Header").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Header must be written before any other content"
+    );
+}
+
+#[test]
+fn airspace_writer_empty() {
+    let writer = AirspaceWriter::new(Vec::new());
+    assert!(writer.finish().unwrap().is_empty());
+}
+
+#[test]
+fn write_constructed_geometries() {
+    let zone = |name: &str, geom| Airspace {
+        name: name.to_string(),
+        class: Class::Restricted,
+        type_: None,
+        lower_bound: Altitude::Gnd,
+        upper_bound: Altitude::FeetAmsl(3000),
+        geom,
+        frequency: None,
+        call_sign: None,
+        transponder_code: None,
+        activation_times: None,
+        label_points: vec![],
+        extensions: vec![],
+    };
+    let center = Coord::from_degrees(47.0, 8.0);
+    let airspaces = [
+        zone(
+            "SECTOR",
+            Geometry::sector(center, 2.0, 5.0, 270.0, 90.0).unwrap(),
+        ),
+        zone(
+            "CORRIDOR",
+            Geometry::corridor(&[center, Coord::from_degrees(47.0, 8.5)], 1852.0).unwrap(),
+        ),
+    ];
+
+    let options = WriterOptions {
+        line_ending: LineEnding::Lf,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    openair::write_with(&mut buf, &airspaces, &options).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+
+    insta::assert_snapshot!(output, @r"
+    AC R
+    AN SECTOR
+    AL GND
+    AH 3000ft AMSL
+    V X=47:00:00 N 008:00:00 E
+    V D=+
+    DA 5, 270, 90
+    V X=47:00:00 N 008:00:00 E
+    V D=-
+    DA 2, 90, 270
+
+    AC R
+    AN CORRIDOR
+    AL GND
+    AH 3000ft AMSL
+    V X=47:00:00 N 008:00:00 E
+    V D=+
+    DA 0.5, 179.82, 359.82
+    V X=47:00:00 N 008:30:00 E
+    V D=+
+    DA 0.5, 0.18, 180.18
+    ");
+}